@@ -0,0 +1,330 @@
+use crate::math::{
+    expression::Expression, expression_type::ExpressionType, function_type::FunctionType,
+    hyperbolic::HyperbolicFunction, polynomial::Polynomial, trigonometric::TrigonometricFunction,
+};
+
+fn trigonometric(function: TrigonometricFunction, argument: &Expression) -> Expression {
+    Expression::apply(FunctionType::Trigonometric(function), argument.clone())
+}
+
+/// `ln|argument|`.
+fn log_abs(argument: Expression) -> Expression {
+    FunctionType::Logarithm.of(Expression::abs(argument))
+}
+
+/// `function^count`, written as a product for powers above one.
+fn trigonometric_power(function: &TrigonometricFunction, count: u32) -> TrigonometricFunction {
+    TrigonometricFunction::from_terms([(1.0, vec![(function.clone(), count)])])
+}
+
+/// Table of antiderivatives `∫ f(u) du`, evaluated at `u`.
+///
+/// Covers sine and cosine, the derivatives of the other four functions
+/// (`sec^2`, `cosec^2`, `sec tan`, `cosec cot`), their powers times such a
+/// derivative (`sin^n cos`, `tan^n sec^2`, ...) and `sin^2`, `cos^2`.
+/// `tan`, `cot`, `sec` and `cosec` on their own integrate to logarithms of
+/// absolute values.
+fn trigonometric_antiderivative(
+    function: &TrigonometricFunction,
+    u: &Expression,
+) -> Option<Expression> {
+    let mut terms = Vec::new();
+    for (coefficient, factors) in function.terms() {
+        terms.extend(
+            term_antiderivative(&factors, u)?
+                .into_iter()
+                .map(|(c, term)| (coefficient * c, term)),
+        );
+    }
+    Some(Expression::sum(
+        terms
+            .into_iter()
+            .map(|(c, term)| Expression::product(vec![Expression::constant(c), term]))
+            .collect(),
+    ))
+}
+
+/// `(coefficient, antiderivative)` pairs for a single product of base
+/// functions, with factors in canonical order.
+fn term_antiderivative(
+    factors: &[(TrigonometricFunction, u32)],
+    u: &Expression,
+) -> Option<Vec<(f64, Expression)>> {
+    use TrigonometricFunction::*;
+
+    let sin_cos = || TrigonometricFunction::product(vec![Sine, Cosine]);
+    Some(match factors {
+        [(Sine, 1)] => vec![(-1.0, trigonometric(Cosine, u))],
+        [(Cosine, 1)] => vec![(1.0, trigonometric(Sine, u))],
+        [(Tangent, 1)] => vec![(-1.0, log_abs(trigonometric(Cosine, u)))],
+        [(Cotangent, 1)] => vec![(1.0, log_abs(trigonometric(Sine, u)))],
+        [(Secant, 1)] => vec![(
+            1.0,
+            log_abs(trigonometric(Secant, u) + trigonometric(Tangent, u)),
+        )],
+        [(Cosecant, 1)] => vec![(
+            -1.0,
+            log_abs(trigonometric(Cosecant, u) + trigonometric(Cotangent, u)),
+        )],
+        [(Secant, 2)] => vec![(1.0, trigonometric(Tangent, u))],
+        [(Cosecant, 2)] => vec![(-1.0, trigonometric(Cotangent, u))],
+        [(Sine, 2)] => vec![(0.5, u.clone()), (-0.5, trigonometric(sin_cos(), u))],
+        [(Cosine, 2)] => vec![(0.5, u.clone()), (0.5, trigonometric(sin_cos(), u))],
+        [(Sine, n), (Cosine, 1)] => vec![(
+            1.0 / (*n as f64 + 1.0),
+            trigonometric(trigonometric_power(&Sine, n + 1), u),
+        )],
+        [(Sine, 1), (Cosine, n)] => vec![(
+            -1.0 / (*n as f64 + 1.0),
+            trigonometric(trigonometric_power(&Cosine, n + 1), u),
+        )],
+        [(Tangent, n), (Secant, 2)] => vec![(
+            1.0 / (*n as f64 + 1.0),
+            trigonometric(trigonometric_power(&Tangent, n + 1), u),
+        )],
+        [(Cotangent, n), (Cosecant, 2)] => vec![(
+            -1.0 / (*n as f64 + 1.0),
+            trigonometric(trigonometric_power(&Cotangent, n + 1), u),
+        )],
+        [(Tangent, 1), (Secant, n)] => vec![(
+            1.0 / *n as f64,
+            trigonometric(trigonometric_power(&Secant, *n), u),
+        )],
+        [(Cotangent, 1), (Cosecant, n)] => vec![(
+            -1.0 / *n as f64,
+            trigonometric(trigonometric_power(&Cosecant, *n), u),
+        )],
+        _ => return None,
+    })
+}
+
+/// `∫ f(u) du` evaluated at `u`, for any function with a table entry.
+fn antiderivative(function: &FunctionType, u: &Expression) -> Option<Expression> {
+    match function {
+        FunctionType::None => Some(Expression::product(vec![
+            Expression::constant(0.5),
+            u.clone(),
+            u.clone(),
+        ])),
+        FunctionType::Polynomial(ref value) => Some(Expression::apply(
+            FunctionType::Polynomial(value.integral(0.0)),
+            u.clone(),
+        )),
+        FunctionType::Trigonometric(ref value) => trigonometric_antiderivative(value, u),
+        FunctionType::Hyperbolic(HyperbolicFunction::Sine) => {
+            Some(HyperbolicFunction::Cosine.of(u.clone()))
+        }
+        FunctionType::Hyperbolic(HyperbolicFunction::Cosine) => {
+            Some(HyperbolicFunction::Sine.of(u.clone()))
+        }
+        FunctionType::Exponential => Some(FunctionType::Exponential.of(u.clone())),
+        // u ln(u) - u, scaled for other bases.
+        FunctionType::Logarithm | FunctionType::LogarithmBase(_) => {
+            let scale = match *function {
+                FunctionType::LogarithmBase(base) => base.ln().recip(),
+                _ => 1.0,
+            };
+            Some(scale * (u * FunctionType::Logarithm.of(u.clone()) - u))
+        }
+        FunctionType::SquareRoot => Some(2.0 / 3.0 * FunctionType::Power(1.5).of(u.clone())),
+        FunctionType::Power(-1.0) => Some(log_abs(u.clone())),
+        FunctionType::Power(r) => {
+            Some((r + 1.0).recip() * FunctionType::Power(r + 1.0).of(u.clone()))
+        }
+        FunctionType::Hyperbolic(_)
+        | FunctionType::InverseHyperbolic(_)
+        | FunctionType::InverseTrigonometric(_)
+        | FunctionType::Special(_)
+        | FunctionType::Custom(_)
+        | FunctionType::Lambda(_) => None,
+    }
+}
+
+/// The trigonometric function `∫ f(u) du` for sine and cosine, which stay
+/// within the family and so can be integrated repeatedly.
+fn trigonometric_integral(function: &TrigonometricFunction) -> Option<TrigonometricFunction> {
+    let terms = function
+        .terms()
+        .into_iter()
+        .map(|(c, factors)| match factors.as_slice() {
+            [(TrigonometricFunction::Sine, 1)] => {
+                Some((-c, vec![(TrigonometricFunction::Cosine, 1)]))
+            }
+            [(TrigonometricFunction::Cosine, 1)] => {
+                Some((c, vec![(TrigonometricFunction::Sine, 1)]))
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(TrigonometricFunction::from_terms(terms))
+}
+
+/// Returns `k` such that `value == k * target`, if there is one.
+fn proportion(value: &Polynomial<f64>, target: &Polynomial<f64>) -> Option<f64> {
+    let value = value.clone().normalized();
+    let target = target.clone().normalized();
+    if value.deg != target.deg || target.is_zero() {
+        return None;
+    }
+    let k = value.poly[value.deg as usize] / target.poly[target.deg as usize];
+    value
+        .poly
+        .iter()
+        .zip(target.poly.iter())
+        .all(|(v, t)| (v - k * t).abs() <= 1e-12 * v.abs().max(1.0))
+        .then_some(k)
+}
+
+/// `∫ q(x) f(p(x)) dx`.
+fn integrate_composition(
+    q: &Polynomial<f64>,
+    function: &FunctionType,
+    p: &Polynomial<f64>,
+) -> Option<Expression> {
+    let u = Expression::polynomial(p.clone());
+    let derivative = p.derivative();
+
+    // Substitution u = p(x), when q is a multiple of p'. This includes a
+    // constant q with a linear p.
+    if let Some(k) = proportion(q, &derivative) {
+        return Some(Expression::product(vec![
+            Expression::constant(k),
+            antiderivative(function, &u)?,
+        ]));
+    }
+
+    // Integration by parts for q(x) times sine or cosine of a linear
+    // argument: ∫ q g = q G1 - q' G2 + q'' G3 - ...
+    let trig = match function {
+        FunctionType::Trigonometric(ref value) if p.clone().normalized().deg == 1 => value,
+        _ => return None,
+    };
+    let a = p.poly[1];
+    let mut g = trig.clone();
+    let mut q = q.clone().normalized();
+    let mut scale = 1.0;
+    let mut terms = Vec::new();
+    while !q.is_zero() {
+        g = trigonometric_integral(&g)?;
+        scale /= a;
+        terms.push(Expression::product(vec![
+            Expression::constant(scale),
+            Expression::polynomial(q.clone()),
+            trigonometric(g.clone(), &u),
+        ]));
+        q = q.derivative() * -1.0;
+    }
+    Some(Expression::sum(terms))
+}
+
+/// `factor` as a list of factors, with a positive integer power of a
+/// function written out as repeated copies, e.g. `sin(x)^2`, and a
+/// constant power of a polynomial as the power function of it.
+fn repeated(factor: &Expression) -> Vec<Expression> {
+    match factor {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Power(ref base, ref exponent),
+        } => match exponent.as_constant() {
+            Some(n) if n >= 1.0 && n.fract() == 0.0 && base.function != FunctionType::None => {
+                vec![*base.clone(); n as usize]
+            }
+            Some(r) if base.as_polynomial().is_some() => {
+                vec![FunctionType::Power(r).of(*base.clone())]
+            }
+            _ => vec![factor.clone()],
+        },
+        _ => vec![factor.clone()],
+    }
+}
+
+/// Splits `expr` into `q(x) * f(p(x))` when it is a polynomial times a
+/// single function (or product of trigonometric functions) of a
+/// polynomial.
+fn as_composition(expr: &Expression) -> Option<(Polynomial<f64>, FunctionType, Polynomial<f64>)> {
+    let one = Polynomial {
+        poly: vec![1.0],
+        deg: 0,
+    };
+    let factors = match expr {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::MultipliedExpressions(ref value),
+        } => value.as_slice(),
+        _ => std::slice::from_ref(expr),
+    };
+
+    let mut q = one;
+    let mut inner: Option<(FunctionType, Polynomial<f64>)> = None;
+    for factor in factors.iter().flat_map(repeated) {
+        if let Some(value) = factor.as_polynomial() {
+            q *= &value;
+            continue;
+        }
+        let p = match factor.input {
+            ExpressionType::Polynomial(ref value) => value.clone(),
+            _ => return None,
+        };
+        inner = match (inner, &factor.function) {
+            (None, function) => Some((function.clone(), p)),
+            // Trigonometric functions of the same argument combine into a
+            // product, e.g. `sec(x) * tan(x)`.
+            (
+                Some((FunctionType::Trigonometric(previous), previous_p)),
+                FunctionType::Trigonometric(current),
+            ) if previous_p == p => Some((
+                FunctionType::Trigonometric(TrigonometricFunction::product(vec![
+                    previous,
+                    current.clone(),
+                ])),
+                p,
+            )),
+            _ => return None,
+        };
+    }
+    inner.map(|(function, p)| (q, function, p))
+}
+
+fn integrate_simplified(expr: &Expression) -> Option<Expression> {
+    if let Some(value) = expr.as_polynomial() {
+        return Some(Expression::polynomial(value.integral(0.0)));
+    }
+    match expr {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Expressions(ref value),
+        } => value
+            .iter()
+            .map(integrate_simplified)
+            .collect::<Option<Vec<Expression>>>()
+            .map(Expression::sum),
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::DividedExpressions(ref num, ref den),
+        } => {
+            let c = den.as_constant()?;
+            Some(Expression::product(vec![
+                Expression::constant(1.0 / c),
+                integrate_simplified(num)?,
+            ]))
+        }
+        _ => {
+            let (q, function, p) = as_composition(expr)?;
+            integrate_composition(&q, &function, &p)
+        }
+    }
+}
+
+impl Expression {
+    /// Returns an antiderivative in `x` (with zero integration constant),
+    /// or `None` when the expression is outside of what the integration
+    /// rules cover.
+    ///
+    /// Handled are sums, constant factors, polynomials, the table entries
+    /// for trigonometric functions of a linear argument, substitution when
+    /// a polynomial factor is a multiple of the inner derivative, and
+    /// integration by parts for a polynomial times sine or cosine.
+    pub fn integrate(&self) -> Option<Expression> {
+        integrate_simplified(&self.simplify()).map(|value| value.simplify())
+    }
+}
@@ -0,0 +1,316 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use num_complex::Complex64;
+use num_traits::ToPrimitive;
+
+use crate::math::{
+    expression::Expression,
+    function_type::FunctionType,
+    polynomial::{Polynomial, PolynomialOperationTypes},
+};
+
+/// A root given by a formula in radicals. `expression` is the root as a
+/// constant expression, available for real roots whose formula stays in
+/// the reals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub value: Complex64,
+    pub expression: Option<Expression>,
+}
+
+impl Solution {
+    fn real(value: f64, expression: Option<Expression>) -> Self {
+        Self {
+            value: Complex64::new(value, 0.0),
+            expression,
+        }
+    }
+
+    fn complex(value: Complex64) -> Self {
+        Self {
+            value,
+            expression: None,
+        }
+    }
+
+    /// Moves the root by `shift`, undoing the substitution that removed the
+    /// second highest term.
+    fn shifted(self, shift: f64) -> Self {
+        Self {
+            value: self.value + shift,
+            expression: self.expression.map(|expression| match shift {
+                0.0 => expression,
+                _ => expression + shift,
+            }),
+        }
+    }
+}
+
+fn square_root(argument: impl Into<Expression>) -> Expression {
+    FunctionType::SquareRoot.of(argument)
+}
+
+fn cube_root(argument: Expression) -> Expression {
+    FunctionType::Power(1.0 / 3.0).of(argument)
+}
+
+/// `constant + expression`, leaving out a vanishing constant.
+fn offset(constant: f64, expression: Expression) -> Expression {
+    match constant {
+        0.0 => expression,
+        _ => constant + expression,
+    }
+}
+
+/// `factor * expression`, leaving out a unit factor.
+fn scaled(factor: f64, expression: Expression) -> Expression {
+    match factor {
+        1.0 => expression,
+        -1.0 => -expression,
+        _ => factor * expression,
+    }
+}
+
+fn sign(value: f64) -> f64 {
+    match value < 0.0 {
+        true => -1.0,
+        false => 1.0,
+    }
+}
+
+/// Root of `a x + b`, none when `a` is zero.
+pub fn linear(a: f64, b: f64) -> Vec<Solution> {
+    match a {
+        0.0 => Vec::new(),
+        _ => vec![Solution::real(-b / a, Some(Expression::constant(-b / a)))],
+    }
+}
+
+/// Roots of `a x^2 + b x + c`.
+///
+/// The root of larger magnitude is `q / a` with
+/// `q = -(b + sign(b) sqrt(b^2 - 4ac)) / 2`, the other is `c / q`, which
+/// avoids the cancellation of the textbook formula.
+pub fn quadratic(a: f64, b: f64, c: f64) -> Vec<Solution> {
+    if a == 0.0 {
+        return linear(b, c);
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        let re = -b / (2.0 * a);
+        let im = (-discriminant).sqrt() / (2.0 * a.abs());
+        return vec![
+            Solution::complex(Complex64::new(re, im)),
+            Solution::complex(Complex64::new(re, -im)),
+        ];
+    }
+    let q = -sign(b) * (b.abs() + discriminant.sqrt()) / 2.0;
+    if discriminant == 0.0 || q == 0.0 {
+        let root = Solution::real(-b / (2.0 * a), Some(Expression::constant(-b / (2.0 * a))));
+        return vec![root.clone(), root];
+    }
+    // `|b| + sqrt(b^2 - 4ac)`, so that `q = -sign(b) sum / 2`.
+    let sum = offset(b.abs(), square_root(discriminant));
+    vec![
+        Solution::real(q / a, Some(scaled(-sign(b) / (2.0 * a), sum.clone()))),
+        Solution::real(c / q, Some(-2.0 * sign(b) * c / sum)),
+    ]
+}
+
+/// Roots of `a x^3 + b x^2 + c x + d`, by Cardano's formula for the
+/// depressed cubic `t^3 + p t + q` with `x = t - b / 3a`.
+///
+/// With three distinct real roots the radicals pass through complex
+/// numbers, the roots are then given in trigonometric form
+/// `2 sqrt(-p/3) cos(acos(3q/2p sqrt(-3/p)) / 3 - 2 pi k / 3)`.
+pub fn cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<Solution> {
+    if a == 0.0 {
+        return quadratic(b, c, d);
+    }
+    let shift = -b / (3.0 * a);
+    let p = (3.0 * a * c - b * b) / (3.0 * a * a);
+    let q = (2.0 * b * b * b - 9.0 * a * b * c + 27.0 * a * a * d) / (27.0 * a * a * a);
+    let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    let roots = if p == 0.0 && q == 0.0 {
+        vec![Solution::real(0.0, Some(Expression::constant(0.0))); 3]
+    } else if discriminant == 0.0 {
+        let double = Solution::real(-1.5 * q / p, Some(Expression::constant(-1.5 * q / p)));
+        vec![
+            Solution::real(3.0 * q / p, Some(Expression::constant(3.0 * q / p))),
+            double.clone(),
+            double,
+        ]
+    } else if discriminant > 0.0 {
+        // `u = cbrt(-q/2 - sign(q) sqrt(D))` adds terms of the same sign, the
+        // second cube root follows from `u v = -p/3`.
+        let u = -sign(q) * (q.abs() / 2.0 + discriminant.sqrt()).cbrt();
+        let v = -p / (3.0 * u);
+        let root = cube_root(offset(q.abs() / 2.0, square_root(discriminant)));
+        let expression = match p {
+            0.0 => scaled(-sign(q), root),
+            _ => scaled(-sign(q), root.clone()) + sign(q) * p / 3.0 / root,
+        };
+        let im = 3f64.sqrt() / 2.0 * (u - v);
+        vec![
+            Solution::real(u + v, Some(expression)),
+            Solution::complex(Complex64::new(-(u + v) / 2.0, im)),
+            Solution::complex(Complex64::new(-(u + v) / 2.0, -im)),
+        ]
+    } else {
+        let radius = 2.0 * (-p / 3.0).sqrt();
+        let cosine = (1.5 * q / p * (-3.0 / p).sqrt()).clamp(-1.0, 1.0);
+        let angle = cosine.acos() / 3.0;
+        let angle_expression = crate::acos!().of(1.5 * q / p * square_root(-3.0 / p)) / 3.0;
+        (0..3)
+            .map(|k| {
+                let offset = 2.0 * PI * k as f64 / 3.0;
+                Solution::real(
+                    radius * (angle - offset).cos(),
+                    Some(
+                        2.0 * square_root(-p / 3.0)
+                            * crate::cos!().of(match k {
+                                0 => angle_expression.clone(),
+                                _ => &angle_expression - offset,
+                            }),
+                    ),
+                )
+            })
+            .collect()
+    };
+    roots.into_iter().map(|root| root.shifted(shift)).collect()
+}
+
+/// The largest root `m` of the resolvent cubic of [`quartic`], with its
+/// expression when Cardano's formula already gives it as positive and to
+/// within rounding error.
+///
+/// For a small `q` the root is close to `q^2 / (2 p^2 - 8 r)` and is lost to
+/// cancellation in Cardano's formula, it is polished by Newton's method and
+/// the expression, which still evaluates to the unpolished value, is
+/// dropped. `None` when no positive root is found, `q` is then negligible
+/// and the quartic is solved as a biquadratic.
+fn resolvent_root(p: f64, q: f64, r: f64) -> Option<(f64, Option<Expression>)> {
+    let linear = 2.0 * p * p - 8.0 * r;
+    let largest = cubic(8.0, 8.0 * p, linear, -q * q)
+        .into_iter()
+        .filter(|m| m.value.im == 0.0)
+        .max_by(|x, y| x.value.re.total_cmp(&y.value.re))?;
+    let cardano = largest.value.re;
+    let mut m = cardano.max(0.0);
+    for _ in 0..16 {
+        let value = ((8.0 * m + 8.0 * p) * m + linear) * m - q * q;
+        let slope = (24.0 * m + 16.0 * p) * m + linear;
+        let step = value / slope;
+        if !step.is_finite() {
+            break;
+        }
+        m -= step;
+        if step.abs() <= f64::EPSILON * m.abs() {
+            break;
+        }
+    }
+    let expression = largest
+        .expression
+        .filter(|_| cardano > 0.0 && (m - cardano).abs() <= 16.0 * f64::EPSILON * m);
+    (m > 0.0 && m.is_normal()).then_some((m, expression))
+}
+
+/// Roots of `a x^4 + b x^3 + c x^2 + d x + e`, by Ferrari's method for
+/// the depressed quartic `y^4 + p y^2 + q y + r` with `x = y - b / 4a`.
+///
+/// A positive root `m` of the resolvent cubic
+/// `8 m^3 + 8 p m^2 + (2 p^2 - 8 r) m - q^2` splits the quartic into two
+/// quadratics, the roots are
+/// `(s sqrt(2m) ± sqrt(-(2p + 2m + s sqrt(2) q / sqrt(m)))) / 2` for
+/// `s = ±1`.
+pub fn quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<Solution> {
+    if a == 0.0 {
+        return cubic(b, c, d, e);
+    }
+    let shift = -b / (4.0 * a);
+    let p = (8.0 * a * c - 3.0 * b * b) / (8.0 * a * a);
+    let q = (b.powi(3) - 4.0 * a * b * c + 8.0 * a * a * d) / (8.0 * a.powi(3));
+    let r = (-3.0 * b.powi(4) + 256.0 * a.powi(3) * e - 64.0 * a * a * b * d
+        + 16.0 * a * b * b * c)
+        / (256.0 * a.powi(4));
+
+    let resolvent = match q {
+        0.0 => None,
+        _ => resolvent_root(p, q, r),
+    };
+    let roots = match resolvent {
+        // Biquadratic, a quadratic in `y^2`.
+        None => quadratic(1.0, p, r)
+            .into_iter()
+            .flat_map(|z| {
+                let expression = match z.value.re >= 0.0 {
+                    true => z.expression.map(square_root),
+                    false => None,
+                };
+                let y = z.value.sqrt();
+                [
+                    Solution {
+                        value: y,
+                        expression: expression.clone(),
+                    },
+                    Solution {
+                        value: -y,
+                        expression: expression.map(|e| -e),
+                    },
+                ]
+            })
+            .collect::<Vec<_>>(),
+        Some((m_value, m_expression)) => [1.0, -1.0]
+            .into_iter()
+            .flat_map(|s| {
+                let inner = -(2.0 * p + 2.0 * m_value + s * SQRT_2 * q / m_value.sqrt());
+                let outer = s * (2.0 * m_value).sqrt();
+                let expression = m_expression.as_ref().filter(|_| inner >= 0.0).map(|m| {
+                    let inner =
+                        offset(-2.0 * p, -2.0 * m - s * SQRT_2 * q / square_root(m.clone()));
+                    (scaled(s, square_root(2.0 * m)), square_root(inner))
+                });
+                let root = Complex64::new(inner, 0.0).sqrt();
+                [1.0, -1.0].map(|t| Solution {
+                    value: (outer + t * root) / 2.0,
+                    expression: expression
+                        .clone()
+                        .map(|(outer, root)| (outer + scaled(t, root)) / 2.0),
+                })
+            })
+            .collect(),
+    };
+    roots.into_iter().map(|root| root.shifted(shift)).collect()
+}
+
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + Copy + ToPrimitive,
+{
+    /// The roots of a polynomial of degree at most four by the formulas of
+    /// [`quadratic`], [`cubic`] and [`quartic`], each repeated root listed
+    /// once per multiplicity. `None` for higher degrees and the zero
+    /// polynomial.
+    pub fn solve(&self) -> Option<Vec<Solution>> {
+        let p = Polynomial {
+            poly: self
+                .poly
+                .iter()
+                .map(|c| c.to_f64().unwrap())
+                .collect::<Vec<f64>>(),
+            deg: self.deg,
+        }
+        .normalized();
+        let c = |k: usize| p.poly[p.deg as usize - k];
+        match p.deg {
+            _ if p.is_zero() => None,
+            0 => Some(Vec::new()),
+            1 => Some(linear(c(0), c(1))),
+            2 => Some(quadratic(c(0), c(1), c(2))),
+            3 => Some(cubic(c(0), c(1), c(2), c(3))),
+            4 => Some(quartic(c(0), c(1), c(2), c(3), c(4))),
+            _ => None,
+        }
+    }
+}
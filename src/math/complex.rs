@@ -0,0 +1,357 @@
+use std::ops::{Add, Mul, Sub};
+
+pub use num_complex::{Complex, Complex64};
+use num_traits::{Num, Zero};
+
+use crate::math::{
+    expression::Expression,
+    expression_type::ExpressionType,
+    func_traits::VariableFunction,
+    function_type::FunctionType,
+    hyperbolic::{HyperbolicFunction, InverseHyperbolicFunction},
+    inverse_trigonometric::InverseTrigonometricFunction,
+    polynomial::{IntegralCoefficient, Polynomial, PolynomialOperationTypes},
+    render::{DisplayCoefficient, Precedence, Renderer},
+    trigonometric::TrigonometricFunction,
+    variable::{Env, EvaluationError},
+};
+
+/// Applies a function without a complex extension, NaN off the real axis.
+fn real_only(z: Complex64, f: impl FnOnce(f64) -> f64) -> Complex64 {
+    match z.im {
+        0.0 => Complex64::new(f(z.re), 0.0),
+        _ => Complex64::new(f64::NAN, f64::NAN),
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + Copy,
+{
+    /// Evaluates the polynomial at `x` by Horner's scheme, `x` may be of a
+    /// wider type than the coefficients, e.g. a complex point of a real
+    /// polynomial.
+    pub fn evaluate_at<U>(&self, x: U) -> U
+    where
+        U: Copy + Zero + Mul<Output = U> + Add<T, Output = U>,
+    {
+        self.poly
+            .iter()
+            .rev()
+            .fold(U::zero(), |accumulator, c| accumulator * x + *c)
+    }
+}
+
+/// Real and imaginary coefficients are written like real ones, e.g.
+/// `-2i x`, others keep their sign inside, e.g. `(1 - 2i)x`.
+impl<T> DisplayCoefficient for Complex<T>
+where
+    T: DisplayCoefficient + Num,
+{
+    fn split_sign(self) -> (bool, Self) {
+        match (self.re.is_zero(), self.im.is_zero()) {
+            (_, true) => {
+                let (negative, re) = self.re.split_sign();
+                (negative, Complex::new(re, T::zero()))
+            }
+            (true, false) => {
+                let (negative, im) = self.im.split_sign();
+                (negative, Complex::new(T::zero(), im))
+            }
+            (false, false) => (false, self),
+        }
+    }
+
+    fn render(self, renderer: &Renderer) -> (String, Precedence) {
+        let (negative, im) = self.im.split_sign();
+        let imaginary = match im.is_one() {
+            true => "i".to_string(),
+            false => format!("{}i", im.render(renderer).0),
+        };
+        match (self.re.is_zero(), self.im.is_zero()) {
+            (_, true) => self.re.render(renderer),
+            (true, false) => (imaginary, Precedence::Product),
+            (false, false) => {
+                let (negative_re, re) = self.re.split_sign();
+                let real = match negative_re {
+                    true => format!("-{}", re.render(renderer).0),
+                    false => re.render(renderer).0,
+                };
+                let sign = match negative {
+                    true => "-",
+                    false => "+",
+                };
+                (format!("{} {} {}", real, sign, imaginary), Precedence::Sum)
+            }
+        }
+    }
+}
+
+impl<T> IntegralCoefficient for Complex<T>
+where
+    T: IntegralCoefficient,
+    T::Output: Num,
+{
+    type Output = Complex<T::Output>;
+
+    fn promote(self) -> Complex<T::Output> {
+        Complex::new(self.re.promote(), self.im.promote())
+    }
+}
+
+impl<'b, T> Mul<&'b Polynomial<Complex<T>>> for Complex<T>
+where
+    T: PolynomialOperationTypes + Copy + Num,
+{
+    type Output = Polynomial<Complex<T>>;
+
+    fn mul(self, rhs: &'b Polynomial<Complex<T>>) -> Self::Output {
+        Polynomial {
+            poly: rhs.poly.iter().map(|c| self * c).collect(),
+            deg: rhs.deg,
+        }
+    }
+}
+
+impl<T> Mul<Polynomial<Complex<T>>> for Complex<T>
+where
+    T: PolynomialOperationTypes + Copy + Num,
+{
+    type Output = Polynomial<Complex<T>>;
+
+    fn mul(self, rhs: Polynomial<Complex<T>>) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl<'b, T> Add<&'b Polynomial<Complex<T>>> for Complex<T>
+where
+    T: PolynomialOperationTypes + Copy + Num,
+{
+    type Output = Polynomial<Complex<T>>;
+
+    fn add(self, rhs: &'b Polynomial<Complex<T>>) -> Self::Output {
+        let mut value = rhs.clone();
+        value.poly[0] = self + value.poly[0];
+        value
+    }
+}
+
+impl<T> Add<Polynomial<Complex<T>>> for Complex<T>
+where
+    T: PolynomialOperationTypes + Copy + Num,
+{
+    type Output = Polynomial<Complex<T>>;
+
+    fn add(self, rhs: Polynomial<Complex<T>>) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl<'b, T> Sub<&'b Polynomial<Complex<T>>> for Complex<T>
+where
+    T: PolynomialOperationTypes + Copy + Num,
+{
+    type Output = Polynomial<Complex<T>>;
+
+    fn sub(self, rhs: &'b Polynomial<Complex<T>>) -> Self::Output {
+        let mut value = Polynomial {
+            poly: rhs.poly.iter().map(|c| Complex::<T>::zero() - c).collect(),
+            deg: rhs.deg,
+        };
+        value.poly[0] = self - rhs.poly[0];
+        value
+    }
+}
+
+impl<T> Sub<Polynomial<Complex<T>>> for Complex<T>
+where
+    T: PolynomialOperationTypes + Copy + Num,
+{
+    type Output = Polynomial<Complex<T>>;
+
+    fn sub(self, rhs: Polynomial<Complex<T>>) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl Expression {
+    /// Evaluates the expression at the complex point `x = z`, on the
+    /// principal branches of the multivalued functions.
+    ///
+    /// Special, custom and piecewise functions and `atan2` are only defined
+    /// for real arguments and are NaN elsewhere, as are variables other
+    /// than `x`, see [`Expression::evaluate_complex_with`].
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        self.function
+            .evaluate_complex(self.input.evaluate_complex(z))
+    }
+}
+
+impl ExpressionType {
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        match self {
+            Self::Constant(value) => Complex64::new(*value, 0.0),
+            Self::Variable(name) if name == "x" => z,
+            Self::Variable(_) => Complex64::new(f64::NAN, f64::NAN),
+            Self::Polynomial(ref value) => value.evaluate_at(z),
+            Self::Expressions(ref value) => value.iter().map(|c| c.evaluate_complex(z)).sum(),
+            Self::MultipliedExpressions(ref value) => {
+                value.iter().map(|c| c.evaluate_complex(z)).product()
+            }
+            Self::DividedExpressions(num, den) => num.evaluate_complex(z) / den.evaluate_complex(z),
+            Self::Power(base, exponent) => {
+                let (base, exponent) = (base.evaluate_complex(z), exponent.evaluate_complex(z));
+                match exponent.im {
+                    0.0 => base.powf(exponent.re),
+                    _ => base.powc(exponent),
+                }
+            }
+            Self::Atan2(..) | Self::Piecewise(..) => real_only(z, |x| self.evaluate(x)),
+        }
+    }
+}
+
+impl FunctionType {
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        match self {
+            Self::Trigonometric(ref value) => value.evaluate_complex(z),
+            Self::InverseTrigonometric(ref value) => value.evaluate_complex(z),
+            Self::Hyperbolic(ref value) => value.evaluate_complex(z),
+            Self::InverseHyperbolic(ref value) => value.evaluate_complex(z),
+            Self::Special(_) | Self::Custom(_) => real_only(z, |x| self.evaluate(x)),
+            Self::Polynomial(ref value) => value.evaluate_at(z),
+            Self::Logarithm => z.ln(),
+            Self::LogarithmBase(base) => z.ln() / base.ln(),
+            Self::Exponential => z.exp(),
+            Self::SquareRoot => z.sqrt(),
+            Self::Power(r) => z.powf(*r),
+            Self::Lambda(ref expr) => expr.evaluate_complex(z),
+            Self::None => z,
+        }
+    }
+}
+
+impl Expression {
+    /// Evaluates the expression at the complex point `x = z` with the other
+    /// variables bound to the real values in `env`, unlike
+    /// [`Expression::evaluate_complex`] which is NaN on them.
+    pub fn evaluate_complex_with(
+        &self,
+        z: Complex64,
+        env: &Env,
+    ) -> Result<Complex64, EvaluationError> {
+        self.function
+            .evaluate_complex_with(self.input.evaluate_complex_with(z, env)?, env)
+    }
+}
+
+impl ExpressionType {
+    pub fn evaluate_complex_with(
+        &self,
+        z: Complex64,
+        env: &Env,
+    ) -> Result<Complex64, EvaluationError> {
+        Ok(match self {
+            Self::Variable(name) if name == "x" => z,
+            Self::Variable(name) => Complex64::new(env.get(name)?, 0.0),
+            Self::Expressions(ref value) => value
+                .iter()
+                .map(|c| c.evaluate_complex_with(z, env))
+                .sum::<Result<Complex64, EvaluationError>>()?,
+            Self::MultipliedExpressions(ref value) => value
+                .iter()
+                .map(|c| c.evaluate_complex_with(z, env))
+                .product::<Result<Complex64, EvaluationError>>()?,
+            Self::DividedExpressions(num, den) => {
+                num.evaluate_complex_with(z, env)? / den.evaluate_complex_with(z, env)?
+            }
+            Self::Power(base, exponent) => {
+                let base = base.evaluate_complex_with(z, env)?;
+                let exponent = exponent.evaluate_complex_with(z, env)?;
+                match exponent.im {
+                    0.0 => base.powf(exponent.re),
+                    _ => base.powc(exponent),
+                }
+            }
+            Self::Atan2(..) | Self::Piecewise(..) => match z.im {
+                0.0 => Complex64::new(self.evaluate_with(&env.clone().with("x", z.re))?, 0.0),
+                _ => Complex64::new(f64::NAN, f64::NAN),
+            },
+            Self::Constant(_) | Self::Polynomial(_) => self.evaluate_complex(z),
+        })
+    }
+}
+
+impl FunctionType {
+    /// Applies the function to `value`, a lambda sees the other variables
+    /// of `env` and `value` as `x`.
+    pub fn evaluate_complex_with(
+        &self,
+        value: Complex64,
+        env: &Env,
+    ) -> Result<Complex64, EvaluationError> {
+        match self {
+            Self::Lambda(ref expr) => expr.evaluate_complex_with(value, env),
+            _ => Ok(self.evaluate_complex(value)),
+        }
+    }
+}
+
+impl TrigonometricFunction {
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        match self {
+            Self::Sine => z.sin(),
+            Self::Cosine => z.cos(),
+            Self::Tangent => z.tan(),
+            Self::Cotangent => z.tan().inv(),
+            Self::Secant => z.cos().inv(),
+            Self::Cosecant => z.sin().inv(),
+            Self::Product(coefficient, ref factors) => factors
+                .iter()
+                .fold(Complex64::new(*coefficient, 0.0), |v, (f, n)| {
+                    v * f.evaluate_complex(z).powu(*n)
+                }),
+            Self::Sum(ref terms) => terms.iter().map(|t| t.evaluate_complex(z)).sum(),
+        }
+    }
+}
+
+impl InverseTrigonometricFunction {
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        match self {
+            Self::Arcsine => z.asin(),
+            Self::Arccosine => z.acos(),
+            Self::Arctangent => z.atan(),
+            Self::Arccotangent => std::f64::consts::FRAC_PI_2 - z.atan(),
+            Self::Arcsecant => z.inv().acos(),
+            Self::Arccosecant => z.inv().asin(),
+        }
+    }
+}
+
+impl HyperbolicFunction {
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        match self {
+            Self::Sine => z.sinh(),
+            Self::Cosine => z.cosh(),
+            Self::Tangent => z.tanh(),
+            Self::Cotangent => z.tanh().inv(),
+            Self::Secant => z.cosh().inv(),
+            Self::Cosecant => z.sinh().inv(),
+        }
+    }
+}
+
+impl InverseHyperbolicFunction {
+    pub fn evaluate_complex(&self, z: Complex64) -> Complex64 {
+        match self {
+            Self::Arsine => z.asinh(),
+            Self::Arcosine => z.acosh(),
+            Self::Artangent => z.atanh(),
+            Self::Arcotangent => z.inv().atanh(),
+            Self::Arsecant => z.inv().acosh(),
+            Self::Arcosecant => z.inv().asinh(),
+        }
+    }
+}
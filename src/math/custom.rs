@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+
+use crate::math::{
+    expression::Expression,
+    func_traits::VariableFunction,
+    function_type::FunctionType,
+    parser::{self, ParseError},
+    render::Renderer,
+};
+
+/// A function supplied by the user, see [`FunctionType::Custom`].
+pub trait CustomFunction: Debug + Send + Sync {
+    /// Name used by the parser and printers, unique within a [`Registry`].
+    fn name(&self) -> &str;
+
+    fn evaluate(&self, x: f64) -> f64;
+
+    /// The derivative as an expression in `x`. Without one the function is
+    /// differentiated numerically.
+    fn derivative(&self) -> Option<Expression> {
+        None
+    }
+
+    fn inverse(&self) -> Option<FunctionType> {
+        None
+    }
+}
+
+/// Shared handle to a [`CustomFunction`], handles are equal when they
+/// share the function, not merely its name.
+#[derive(Debug, Clone)]
+pub struct Custom {
+    function: Arc<dyn CustomFunction>,
+    /// Computed once, so that every derivative of the handle is the same.
+    derivative: Arc<OnceLock<FunctionType>>,
+}
+
+impl Custom {
+    pub fn new(function: impl CustomFunction + 'static) -> Self {
+        Self {
+            function: Arc::new(function),
+            derivative: Arc::default(),
+        }
+    }
+
+    /// Applies the function to `argument`.
+    pub fn of(&self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(FunctionType::Custom(self.clone()), argument.into())
+    }
+
+    /// The registered derivative, or a central difference approximation
+    /// named `f'` when there is none.
+    pub fn derivative(&self) -> FunctionType {
+        self.derivative
+            .get_or_init(|| match self.function.derivative() {
+                Some(value) => FunctionType::Lambda(Box::new(value)),
+                None => FunctionType::Custom(Custom::new(NumericDerivative {
+                    name: format!("{}'", self.name()),
+                    function: self.clone(),
+                })),
+            })
+            .clone()
+    }
+}
+
+impl Deref for Custom {
+    type Target = dyn CustomFunction;
+
+    fn deref(&self) -> &Self::Target {
+        self.function.as_ref()
+    }
+}
+
+impl PartialEq for Custom {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.function, &other.function)
+    }
+}
+
+impl Display for Custom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&Renderer::plain(None).custom(self))
+    }
+}
+
+/// Derivative of a function without a symbolic one, `f'(x)` is
+/// approximated by `(f(x + h) - f(x - h)) / 2h`.
+#[derive(Debug)]
+struct NumericDerivative {
+    name: String,
+    function: Custom,
+}
+
+impl CustomFunction for NumericDerivative {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn evaluate(&self, x: f64) -> f64 {
+        // The step balances truncation against rounding error.
+        let h = f64::EPSILON.cbrt() * x.abs().max(1.0);
+        (self.function.evaluate(x + h) - self.function.evaluate(x - h)) / (2.0 * h)
+    }
+}
+
+/// Custom functions by name, makes them available to
+/// [`Registry::parse`].
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    functions: HashMap<String, Custom>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the registry with `function` added.
+    pub fn with(mut self, function: impl CustomFunction + 'static) -> Self {
+        self.register(function);
+        self
+    }
+
+    /// Adds `function`, replacing one with the same name, and returns its
+    /// handle.
+    pub fn register(&mut self, function: impl CustomFunction + 'static) -> Custom {
+        let function = Custom::new(function);
+        self.functions
+            .insert(function.name().to_string(), function.clone());
+        function
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Custom> {
+        self.functions.get(name)
+    }
+
+    /// The function called `name`, each trailing `'` takes a derivative,
+    /// e.g. `f''` for the second derivative of `f`.
+    pub fn function(&self, name: &str) -> Option<FunctionType> {
+        let base = name.trim_end_matches('\'');
+        let function = FunctionType::Custom(self.get(base)?.clone());
+        Some((base.len()..name.len()).fold(function, |f, _| f.derivative()))
+    }
+
+    /// Parses `input` like [`parser::parse`], with the registered functions
+    /// callable by name. Built-in functions take precedence.
+    pub fn parse(&self, input: &str) -> Result<Expression, ParseError> {
+        parser::parse_with(input, self)
+    }
+}
@@ -1,7 +1,8 @@
 use std::fmt::Display;
 
 use super::{
-    expression_type::ExpressionType, func_traits::VariableFunction,
+    expression_type::ExpressionType, func_traits::VariableFunction, parser::ParseError,
+    polynomial::Polynomial, render::Renderer,
 };
 use crate::math::function_type::FunctionType;
 
@@ -13,12 +14,13 @@ pub struct Expression {
 
 impl Display for Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.function {
-            FunctionType::None => f.write_str(format!("{}", self.input.to_string()).as_str()),
-            _ => f.write_str(
-                format!("{}({})", self.function.to_string(), self.input.to_string()).as_str(),
-            ),
-        }
+        f.write_str(&Renderer::plain(f.precision()).expression(self).0)
+    }
+}
+
+impl Default for Expression {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -30,150 +32,239 @@ impl Expression {
         }
     }
 
-    pub fn new_from(expr: &Vec<Expression>) -> Expression {
+    pub fn new_from(expr: &[Expression]) -> Expression {
         Expression {
             function: FunctionType::None,
-            input: ExpressionType::MultipliedExpressions(expr.clone()),
+            input: ExpressionType::MultipliedExpressions(expr.to_vec()),
         }
     }
 
-    pub fn is_constant(&self) -> bool {
-        self.input.is_constant()
+    pub fn constant(value: f64) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Constant(value),
+        }
     }
 
-    pub fn simplify(&self) {
-        
+    pub fn sum(terms: Vec<Expression>) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Expressions(terms),
+        }
     }
-}
 
-impl VariableFunction for Expression {
-    fn evaluate(&self, x: f64) -> f64 {
-        let input_value = x;
-
-        match &self.input {
-            ExpressionType::Constant(value) => self.function.evaluate(*value),
-            ExpressionType::Expressions(value) => self.function.evaluate(
-                value
-                    .into_iter()
-                    .map(|expr| expr.evaluate(input_value))
-                    .reduce(|prev, curr| prev + curr)
-                    .unwrap(),
-            ),
-            ExpressionType::MultipliedExpressions(value) => self.function.evaluate(
-                value
-                    .into_iter()
-                    .map(|expr| expr.evaluate(input_value))
-                    .reduce(|prev, curr| prev * curr)
-                    .unwrap(),
-            ),
-            ExpressionType::DividedExpressions(num, den) => {
-                self.function.evaluate(num.evaluate(x) / den.evaluate(x))
-            }
-            ExpressionType::Polynomial(value) => {
-                self.function.evaluate(value.evaluate(input_value))
-            }
+    pub fn product(factors: Vec<Expression>) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::MultipliedExpressions(factors),
         }
     }
 
-    fn derivative(&self) -> Self {
-        // Derivative depends on what the input for the function is:
-        // If the input is constucted that contains
-        // independent expression,
-        // we convert it into a multiplied function
-        // else, we return as constant
-        match &self.input {
-            ExpressionType::Constant(_value) => Expression {
-                function: self.function.derivative(),
-                input: self.input.derivative(),
-            },
-            ExpressionType::MultipliedExpressions(ref value) => Expression {
+    pub fn quotient(num: Expression, den: Expression) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::DividedExpressions(Box::new(num), Box::new(den)),
+        }
+    }
+
+    pub fn power(base: Expression, exponent: Expression) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Power(Box::new(base), Box::new(exponent)),
+        }
+    }
+
+    /// The two-argument arctangent, the angle of the point `(x, y)`.
+    pub fn atan2(y: Expression, x: Expression) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Atan2(Box::new(y), Box::new(x)),
+        }
+    }
+
+    /// The variable `name`, where `x` is the polynomial `x`.
+    pub fn variable(name: &str) -> Expression {
+        match name {
+            "x" => Expression::polynomial(Polynomial {
+                poly: vec![0.0, 1.0],
+                deg: 1,
+            }),
+            _ => Expression {
                 function: FunctionType::None,
-                input: ExpressionType::Expressions(
-                    value
-                        .iter()
-                        .filter(|c| !c.is_constant())
-                        .map(|c| {
-                            ExpressionType::MultipliedExpressions(
-                                [
-                                    value
-                                        .iter()
-                                        .filter(|p| **p != *c)
-                                        .map(|p| p.clone())
-                                        .collect::<Vec<Expression>>(),
-                                    vec![c.derivative()],
-                                ]
-                                .concat(),
-                            )
-                        })
-                        .map(|der| Expression {
-                            function: FunctionType::None,
-                            input: der,
-                        })
-                        .collect::<Vec<Expression>>(),
-                ),
+                input: ExpressionType::Variable(name.to_string()),
             },
-            ExpressionType::Expressions(ref value) => Expression {
+        }
+    }
+
+    /// The polynomial `value` in the variable `name`, i.e. `value` applied to
+    /// `name` unless `name` is `x`.
+    pub fn polynomial_in(value: Polynomial<f64>, name: &str) -> Expression {
+        match name {
+            "x" => Expression::polynomial(value),
+            _ => Expression::apply(FunctionType::Polynomial(value), Expression::variable(name)),
+        }
+    }
+
+    /// Applies `function` to `argument`, i.e. builds `function(argument)`.
+    pub fn apply(function: FunctionType, argument: Expression) -> Expression {
+        match argument {
+            Expression {
                 function: FunctionType::None,
-                input: ExpressionType::Expressions(
-                    value
-                        .iter()
-                        .filter(|c| !c.is_constant())
-                        .map(|c| c.derivative())
-                        .collect::<Vec<Expression>>(),
-                ),
+                input,
+            } => Expression { function, input },
+            nested => Expression {
+                function,
+                input: ExpressionType::Expressions(vec![nested]),
             },
-            ExpressionType::DividedExpressions(ref num, ref den) => Expression {
-                function: FunctionType::None,
-                input: ExpressionType::DividedExpressions(
-                    Box::new(Expression {
-                        function: FunctionType::None,
-                        input: ExpressionType::Expressions(vec![
-                            Expression {
-                                function: FunctionType::None,
-                                input: ExpressionType::MultipliedExpressions(vec![
-                                    num.derivative(),
-                                    *den.clone(),
-                                ]),
-                            },
-                            Expression {
-                                function: FunctionType::None,
-                                input: ExpressionType::MultipliedExpressions(vec![
-                                    den.derivative(),
-                                    *num.clone(),
-                                    Expression {
-                                        function: FunctionType::None,
-                                        input: ExpressionType::Constant(-1.0f64),
-                                    },
-                                ]),
-                            },
-                        ]),
-                    }),
-                    Box::new(Expression {
-                        function: FunctionType::None,
-                        input: ExpressionType::MultipliedExpressions(vec![
-                            *den.clone(),
-                            *den.clone(),
-                        ]),
-                    }),
-                ),
+        }
+    }
+
+    /// Wraps a polynomial, demoting it to a constant when it has no
+    /// non-constant terms left.
+    pub fn polynomial(value: Polynomial<f64>) -> Expression {
+        let value = value.normalized();
+        Expression {
+            function: FunctionType::None,
+            input: match value.deg {
+                0 => ExpressionType::Constant(value.poly[0]),
+                _ => ExpressionType::Polynomial(value),
             },
-            ExpressionType::Polynomial(_) => Expression {
-                function: FunctionType::None,
-                input: ExpressionType::MultipliedExpressions(vec![
-                    Expression {
-                        function: FunctionType::None,
-                        input: self.input.derivative(),
-                    },
-                    Expression {
-                        function: self.function.derivative(),
-                        input: self.input.clone(),
-                    },
-                ]),
+        }
+    }
+
+    /// Returns the polynomial this expression stands for, if it is a bare
+    /// polynomial or constant.
+    pub fn as_polynomial(&self) -> Option<Polynomial<f64>> {
+        match (&self.function, &self.input) {
+            (FunctionType::None, ExpressionType::Polynomial(value)) => Some(value.clone()),
+            (FunctionType::None, ExpressionType::Constant(value)) => Some(Polynomial {
+                poly: vec![*value],
+                deg: 0,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of this expression if it is a bare constant.
+    pub fn as_constant(&self) -> Option<f64> {
+        self.as_polynomial()
+            .map(Polynomial::normalized)
+            .filter(|value| value.deg == 0)
+            .map(|value| value.poly[0])
+    }
+
+    /// Parses an infix expression in `x`, see [`super::parser::parse`].
+    pub fn parse(input: &str) -> Result<Expression, ParseError> {
+        super::parser::parse(input)
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.input.is_constant()
+    }
+
+    pub fn depends_on(&self, variable: &str) -> bool {
+        self.input.depends_on(variable)
+    }
+
+    /// Partial derivative with respect to `variable`, the other variables
+    /// are held constant.
+    pub fn derivative_wrt(&self, variable: &str) -> Expression {
+        // Chain rule: for `f(g(x))` the derivative is `g'(x) * f'(g(x))`,
+        // where `g'` comes from the input and applies at every level of
+        // nesting through `ExpressionType::derivative_wrt`.
+        if !self.depends_on(variable) {
+            return Expression::constant(0.0);
+        }
+        let inner = Expression {
+            function: FunctionType::None,
+            input: self.input.derivative_wrt(variable),
+        };
+        match self.function {
+            FunctionType::None => inner,
+            _ => Expression::product(vec![
+                inner,
+                Expression {
+                    function: self.function.derivative(),
+                    input: self.input.clone(),
+                },
+            ]),
+        }
+    }
+
+    /// Replaces every occurrence of `variable` with `replacement`, for `x`
+    /// this includes polynomial inputs, which are composed with
+    /// `replacement` when it is a polynomial itself.
+    pub fn substitute(&self, variable: &str, replacement: &Expression) -> Expression {
+        Expression::apply(
+            self.function.clone(),
+            self.input.substitute(variable, replacement),
+        )
+    }
+
+    /// Partial derivatives with respect to each of `variables`.
+    pub fn gradient(&self, variables: &[&str]) -> Vec<Expression> {
+        variables
+            .iter()
+            .map(|variable| self.derivative_wrt(variable))
+            .collect()
+    }
+
+    /// Matrix of partial derivatives, row `i` is the gradient of
+    /// `functions[i]`.
+    pub fn jacobian(functions: &[Expression], variables: &[&str]) -> Vec<Vec<Expression>> {
+        functions
+            .iter()
+            .map(|function| function.gradient(variables))
+            .collect()
+    }
+
+    /// Matrix of second partial derivatives, the Jacobian of the gradient.
+    pub fn hessian(&self, variables: &[&str]) -> Vec<Vec<Expression>> {
+        Expression::jacobian(&self.gradient(variables), variables)
+    }
+
+    /// Returns an equivalent, smaller expression: constants are folded,
+    /// nested sums and products are flattened, additive zeros and
+    /// multiplicative ones are dropped, products containing zero collapse,
+    /// polynomial terms are merged into a single polynomial and repeated
+    /// factors are collected into powers.
+    ///
+    /// Constants fold as in floating point arithmetic, `0 / 0` and
+    /// `0 * (1 / 0)` become NaN. A zero numerator or factor does absorb
+    /// the non-constant rest, `0 * ln(x)` and `0 / x` become `0` also where
+    /// `ln(x)` or `1 / x` are undefined.
+    pub fn simplify(&self) -> Expression {
+        let input = self.input.simplify();
+        if self.function == FunctionType::None {
+            return input;
+        }
+        if let FunctionType::Lambda(ref value) = self.function {
+            return value.substitute("x", &input).simplify();
+        }
+        if let Some(value) = input.as_constant() {
+            return Expression::constant(self.function.evaluate(value));
+        }
+        Expression {
+            function: self.function.clone(),
+            input: match input {
+                Expression {
+                    function: FunctionType::None,
+                    input,
+                } => input,
+                nested => ExpressionType::Expressions(vec![nested]),
             },
         }
-        // Expression {
-        //     function: FunctionType::Constant,
-        //     input: ExpressionType::Constant(1.0),
-        // }
+    }
+}
+
+impl VariableFunction for Expression {
+    type Derivative = Self;
+
+    fn evaluate(&self, x: f64) -> f64 {
+        self.function.evaluate(self.input.evaluate(x))
+    }
+
+    fn derivative(&self) -> Self {
+        self.derivative_wrt("x")
     }
 }
@@ -1,42 +1,340 @@
 use std::fmt::Display;
 
 use crate::math::function_type::FunctionType;
+use crate::math::piecewise::{Condition, Relation};
 
-use super::{expression::Expression, func_traits::VariableFunction, polynomial::Polynomial};
+use super::{
+    expression::Expression,
+    func_traits::VariableFunction,
+    polynomial::{Polynomial, MAX_EXPANDED_DEGREE},
+    render::Renderer,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExpressionType {
     Expressions(Vec<Expression>),
     Constant(f64),
+    /// A polynomial in `x`. A polynomial in another variable is
+    /// [`FunctionType::Polynomial`] applied to that variable, see
+    /// [`Expression::polynomial_in`].
     Polynomial(Polynomial<f64>),
     MultipliedExpressions(Vec<Expression>),
     DividedExpressions(Box<Expression>, Box<Expression>),
+    /// `base ^ exponent`, both arbitrary expressions.
+    Power(Box<Expression>, Box<Expression>),
+    /// Two-argument arctangent `atan2(y, x)`, the angle of the point
+    /// `(x, y)` in `(-pi, pi]`.
+    Atan2(Box<Expression>, Box<Expression>),
+    /// A named variable other than `x`, see [`crate::math::variable::Env`].
+    Variable(String),
+    /// The value of the first branch whose condition holds, the last
+    /// expression otherwise.
+    Piecewise(Vec<(Condition, Expression)>, Box<Expression>),
 }
 
 impl Display for ExpressionType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let out = match *self {
-            Self::Expressions(ref value) => value
-                .iter()
-                .map(|c| c.to_string())
-                .collect::<Vec<String>>()
-                .join(" + "),
-            Self::Constant(val) => val.to_string(),
-            Self::Polynomial(ref val) => format!("({})", val.to_string()),
-            Self::MultipliedExpressions(ref value) => value
-                .iter()
-                .map(|c| c.to_string())
-                .collect::<Vec<String>>()
-                .join(" * "),
-            Self::DividedExpressions(ref num, ref den) => {
-                format!("({}) / ({})", num.to_string(), den.to_string())
-            }
-        };
-        f.write_str(out.as_str())
+        f.write_str(&Renderer::plain(f.precision()).input(self).0)
+    }
+}
+
+/// The argument of a function applied to `input`, undoing the wrapping of
+/// [`Expression::apply`].
+fn argument(input: &ExpressionType) -> Expression {
+    match input {
+        ExpressionType::Expressions(ref value) if value.len() == 1 => value[0].clone(),
+        _ => Expression {
+            function: FunctionType::None,
+            input: input.clone(),
+        },
+    }
+}
+
+/// Splits a factor into a base and a constant exponent, reading square
+/// roots as `b^0.5`. The exponent is `None` when it is not constant.
+fn as_power(factor: &Expression) -> (Expression, Option<f64>) {
+    match factor {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Power(base, exponent),
+        } => match exponent.as_constant() {
+            Some(c) => (*base.clone(), Some(c)),
+            None => (factor.clone(), None),
+        },
+        Expression {
+            function: FunctionType::SquareRoot,
+            input,
+        } => (argument(input), Some(0.5)),
+        Expression {
+            function: FunctionType::Power(r),
+            input,
+        } => (argument(input), Some(*r)),
+        _ => (factor.clone(), Some(1.0)),
     }
 }
 
 impl ExpressionType {
+    /// Simplifies this input, see [`Expression::simplify`]. The result is
+    /// a bare expression (without an outer function).
+    pub fn simplify(&self) -> Expression {
+        match self {
+            Self::Constant(value) => Expression::constant(*value),
+            Self::Variable(ref name) => Expression::variable(name),
+            Self::Polynomial(ref value) => Expression::polynomial(value.clone()),
+            Self::Expressions(ref value) => {
+                let mut terms: Vec<Expression> = Vec::new();
+                let mut merged: Option<(usize, Polynomial<f64>)> = None;
+                for term in value.iter().flat_map(|c| match c.simplify() {
+                    Expression {
+                        function: FunctionType::None,
+                        input: Self::Expressions(inner),
+                    } => inner,
+                    other => vec![other],
+                }) {
+                    match (term.as_polynomial(), merged.take()) {
+                        (Some(p), Some((position, m))) => merged = Some((position, m + p)),
+                        (Some(p), None) => merged = Some((terms.len(), p)),
+                        (None, previous) => {
+                            merged = previous;
+                            terms.push(term);
+                        }
+                    }
+                }
+                if let Some((position, p)) = merged {
+                    let p = Expression::polynomial(p);
+                    if p.as_constant() != Some(0.0) {
+                        terms.insert(position, p);
+                    }
+                }
+                match terms.len() {
+                    0 => Expression::constant(0.0),
+                    1 => terms.pop().unwrap(),
+                    _ => Expression {
+                        function: FunctionType::None,
+                        input: Self::Expressions(terms),
+                    },
+                }
+            }
+            Self::MultipliedExpressions(ref value) => {
+                let mut factors: Vec<Expression> = Vec::new();
+                let mut merged: Option<Polynomial<f64>> = None;
+                for factor in value.iter().flat_map(|c| match c.simplify() {
+                    Expression {
+                        function: FunctionType::None,
+                        input: Self::MultipliedExpressions(inner),
+                    } => inner,
+                    other => vec![other],
+                }) {
+                    match factor.as_polynomial() {
+                        Some(p) => {
+                            merged = Some(match (merged, factor.as_constant()) {
+                                // Scaling touches every coefficient, so that
+                                // `0 * inf` is NaN.
+                                (Some(m), Some(c)) => m * c,
+                                (Some(m), None) => m * &p,
+                                (None, _) => p,
+                            })
+                        }
+                        None => factors.push(factor),
+                    }
+                }
+                // Repeated factors collapse into a power of their base,
+                // `b^c1 * b^c2 = b^(c1 + c2)` for constant exponents, and
+                // exponentials into one, `exp(a) exp(b) = exp(a + b)`. A
+                // factor without a partner is kept as written.
+                let mut powers: Vec<(Expression, Option<f64>, Vec<Expression>)> = Vec::new();
+                let mut exponentials: Option<(usize, Vec<Expression>)> = None;
+                for factor in factors.drain(..) {
+                    if factor.function == FunctionType::Exponential {
+                        match exponentials {
+                            Some((_, ref mut group)) => group.push(factor),
+                            None => exponentials = Some((powers.len(), vec![factor])),
+                        }
+                        continue;
+                    }
+                    let (base, exponent) = as_power(&factor);
+                    match (
+                        exponent,
+                        powers
+                            .iter_mut()
+                            .find(|(b, e, _)| e.is_some() && *b == base),
+                    ) {
+                        (Some(c), Some((_, Some(e), group))) => {
+                            *e += c;
+                            group.push(factor);
+                        }
+                        (exponent, _) => powers.push((base, exponent, vec![factor])),
+                    }
+                }
+                for (base, exponent, mut group) in powers {
+                    if group.len() == 1 {
+                        factors.push(group.pop().unwrap());
+                        continue;
+                    }
+                    match exponent {
+                        Some(0.0) => {}
+                        Some(1.0) => factors.push(base),
+                        Some(c) => factors.push(
+                            Self::Power(Box::new(base), Box::new(Expression::constant(c)))
+                                .simplify(),
+                        ),
+                        None => factors.push(base),
+                    }
+                }
+                if let Some((position, mut group)) = exponentials {
+                    let exponential = match group.len() {
+                        1 => group.pop().unwrap(),
+                        _ => FunctionType::Exponential.of(Self::Expressions(
+                            group.iter().map(|c| argument(&c.input)).collect(),
+                        )
+                        .simplify()),
+                    };
+                    factors.insert(position.min(factors.len()), exponential);
+                }
+                if let Some(p) = merged {
+                    let p = Expression::polynomial(p);
+                    match p.as_constant() {
+                        Some(0.0) => return Expression::constant(0.0),
+                        Some(1.0) => {}
+                        _ => factors.insert(0, p),
+                    }
+                }
+                match factors.len() {
+                    0 => Expression::constant(1.0),
+                    1 => factors.pop().unwrap(),
+                    _ => Expression {
+                        function: FunctionType::None,
+                        input: Self::MultipliedExpressions(factors),
+                    },
+                }
+            }
+            Self::DividedExpressions(ref num, ref den) => {
+                let num = num.simplify();
+                let den = den.simplify();
+                match (num.as_constant(), den.as_constant()) {
+                    (Some(a), Some(b)) => return Expression::constant(a / b),
+                    (Some(0.0), None) => return Expression::constant(0.0),
+                    _ => {}
+                }
+                match (num.as_polynomial(), den.as_constant()) {
+                    (_, Some(1.0)) => num,
+                    (Some(p), Some(c)) if c != 0.0 => Expression::polynomial(p / c),
+                    _ => Expression {
+                        function: FunctionType::None,
+                        input: Self::DividedExpressions(Box::new(num), Box::new(den)),
+                    },
+                }
+            }
+            Self::Atan2(ref y, ref x) => {
+                let (y, x) = (y.simplify(), x.simplify());
+                match (y.as_constant(), x.as_constant()) {
+                    (Some(y), Some(x)) => Expression::constant(y.atan2(x)),
+                    _ => Expression::atan2(y, x),
+                }
+            }
+            // Branches with a constant condition are dropped, or end the
+            // list when it holds.
+            Self::Piecewise(ref branches, ref otherwise) => {
+                let mut simplified = Vec::new();
+                let mut otherwise = otherwise.simplify();
+                for (condition, value) in branches {
+                    let condition = condition.simplify();
+                    if !condition.is_constant() {
+                        simplified.push((condition, value.simplify()));
+                    } else if condition.holds(0.0) {
+                        otherwise = value.simplify();
+                        break;
+                    }
+                }
+                // Trailing branches with the same value as `otherwise` make
+                // no difference.
+                while simplified
+                    .last()
+                    .is_some_and(|(_, value)| *value == otherwise)
+                {
+                    simplified.pop();
+                }
+                match simplified.len() {
+                    0 => otherwise,
+                    _ => Expression::piecewise(simplified, otherwise),
+                }
+            }
+            Self::Power(ref base, ref exponent) => {
+                let base = base.simplify();
+                let exponent = exponent.simplify();
+                match (base.as_polynomial(), exponent.as_constant()) {
+                    (_, Some(0.0)) => Expression::constant(1.0),
+                    (_, Some(1.0)) => base,
+                    (Some(p), Some(c)) if p.deg == 0 => Expression::constant(p.poly[0].powf(c)),
+                    // Non-negative integer powers of a polynomial are expanded
+                    // up to `MAX_EXPANDED_DEGREE`.
+                    (Some(p), Some(c))
+                        if c > 0.0
+                            && c.fract() == 0.0
+                            && c * p.deg as f64 <= MAX_EXPANDED_DEGREE as f64 =>
+                    {
+                        let mut value = p.clone();
+                        for _ in 1..c as usize {
+                            value *= &p;
+                        }
+                        Expression::polynomial(value)
+                    }
+                    _ => match (as_power(&base), exponent.as_constant()) {
+                        // (b^c)^n = b^(c * n) for an integer n, including
+                        // square roots and powers of functions.
+                        ((inner, Some(c)), Some(n)) if n.fract() == 0.0 && inner != base => {
+                            Self::Power(Box::new(inner), Box::new(Expression::constant(c * n)))
+                                .simplify()
+                        }
+                        _ => Expression::power(base, exponent),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Replaces `variable` with `replacement`, see
+    /// [`Expression::substitute`]. The result is a bare expression.
+    pub fn substitute(&self, variable: &str, replacement: &Expression) -> Expression {
+        let substitute = |c: &Expression| c.substitute(variable, replacement);
+        match self {
+            Self::Variable(ref name) if name == variable => replacement.clone(),
+            Self::Polynomial(ref value) if variable == "x" => match replacement.as_polynomial() {
+                Some(inner) => Expression::polynomial(value.compose(&inner)),
+                None => {
+                    Expression::apply(FunctionType::Polynomial(value.clone()), replacement.clone())
+                }
+            },
+            Self::Constant(_) | Self::Variable(_) | Self::Polynomial(_) => Expression {
+                function: FunctionType::None,
+                input: self.clone(),
+            },
+            Self::Expressions(ref value) => Expression::sum(value.iter().map(substitute).collect()),
+            Self::MultipliedExpressions(ref value) => {
+                Expression::product(value.iter().map(substitute).collect())
+            }
+            Self::DividedExpressions(num, den) => {
+                Expression::quotient(substitute(num), substitute(den))
+            }
+            Self::Power(base, exponent) => {
+                Expression::power(substitute(base), substitute(exponent))
+            }
+            Self::Atan2(y, x) => Expression::atan2(substitute(y), substitute(x)),
+            Self::Piecewise(branches, otherwise) => Expression::piecewise(
+                branches
+                    .iter()
+                    .map(|(condition, value)| {
+                        (
+                            condition.substitute(variable, replacement),
+                            substitute(value),
+                        )
+                    })
+                    .collect(),
+                substitute(otherwise),
+            ),
+        }
+    }
+
     pub fn is_constant(&self) -> bool {
         match &self {
             Self::Constant(_val) => true,
@@ -44,65 +342,200 @@ impl ExpressionType {
             Self::MultipliedExpressions(ref value) => value.iter().all(|c| c.is_constant()),
             Self::Expressions(ref value) => value.iter().all(|c| c.is_constant()),
             Self::Polynomial(ref value) => value.deg == 0,
+            Self::Power(first, second) | Self::Atan2(first, second) => {
+                first.is_constant() && second.is_constant()
+            }
+            Self::Variable(_) => false,
+            Self::Piecewise(branches, otherwise) => {
+                otherwise.is_constant()
+                    && branches
+                        .iter()
+                        .all(|(condition, value)| condition.is_constant() && value.is_constant())
+            }
         }
     }
-}
 
-impl VariableFunction for ExpressionType {
-    fn evaluate(&self, x: f64) -> f64 {
+    /// Whether the value changes with `variable`, polynomials are in `x`.
+    pub fn depends_on(&self, variable: &str) -> bool {
         match &self {
-            Self::Constant(val) => *val,
-            Self::DividedExpressions(num, den) => num.evaluate(x) / den.evaluate(x),
-            Self::Polynomial(ref value) => value.evaluate(x),
-            Self::MultipliedExpressions(ref value) => value
-                .iter()
-                .map(|c| c.evaluate(x))
-                .reduce(|p, c| p * c)
-                .unwrap(),
-            Self::Expressions(ref value) => value
-                .iter()
-                .map(|c| c.evaluate(x))
-                .reduce(|p, c| p + c)
-                .unwrap(),
+            Self::Constant(_) => false,
+            Self::Variable(ref name) => name == variable,
+            Self::Polynomial(ref value) => variable == "x" && value.deg > 0,
+            Self::Expressions(ref value) | Self::MultipliedExpressions(ref value) => {
+                value.iter().any(|c| c.depends_on(variable))
+            }
+            Self::DividedExpressions(first, second)
+            | Self::Power(first, second)
+            | Self::Atan2(first, second) => {
+                first.depends_on(variable) || second.depends_on(variable)
+            }
+            Self::Piecewise(branches, otherwise) => {
+                otherwise.depends_on(variable)
+                    || branches.iter().any(|(condition, value)| {
+                        condition.depends_on(variable) || value.depends_on(variable)
+                    })
+            }
         }
     }
 
-    fn derivative(&self) -> Self {
+    /// Partial derivative with respect to `variable`, the other variables
+    /// are held constant.
+    pub fn derivative_wrt(&self, variable: &str) -> Self {
+        if !self.depends_on(variable) {
+            return Self::Constant(0.0);
+        }
         match &self {
             Self::Constant(ref _value) => Self::Constant(0.0),
+            Self::Variable(ref name) if name == variable => Self::Constant(1.0),
+            Self::Variable(_) => Self::Constant(0.0),
             Self::Expressions(ref value) => Self::Expressions(
                 value
                     .iter()
-                    .filter(|c| !c.is_constant())
-                    .map(|c| c.derivative())
+                    .filter(|c| c.depends_on(variable))
+                    .map(|c| c.derivative_wrt(variable))
                     .collect::<Vec<Expression>>(),
             ),
+            // Product rule, the other factors are picked by position so that
+            // repeated factors such as `sin(x) * sin(x)` are kept.
             Self::MultipliedExpressions(ref value) => Self::Expressions(
                 value
                     .iter()
-                    .filter(|c| !c.is_constant())
-                    .map(|c| {
-                        Self::MultipliedExpressions(
-                            [
-                                value
-                                    .iter()
-                                    .filter(|p| **p != *c)
-                                    .map(|p| p.clone())
-                                    .collect::<Vec<Expression>>(),
-                                vec![c.derivative()],
-                            ]
-                            .concat(),
+                    .enumerate()
+                    .filter(|(_, c)| c.depends_on(variable))
+                    .map(|(index, c)| {
+                        Expression::product(
+                            value
+                                .iter()
+                                .enumerate()
+                                .filter(|(other, _)| *other != index)
+                                .map(|(_, p)| p.clone())
+                                .chain(std::iter::once(c.derivative_wrt(variable)))
+                                .collect::<Vec<Expression>>(),
                         )
                     })
-                    .map(|der| Expression {
-                        function: FunctionType::None,
-                        input: der,
-                    })
                     .collect::<Vec<Expression>>(),
             ),
+            // Polynomials are in `x`, so they only depend on `x`.
             Self::Polynomial(ref value) => Self::Polynomial(value.derivative()),
-            // Self::DividedExpressions(ref num, ref den) => Self::DividedExpressions(d, ())
-            _ => Self::Constant(0.0),
+            // Quotient rule: (n / d)' = (n' * d - d' * n) / (d * d)
+            Self::DividedExpressions(ref num, ref den) => Self::DividedExpressions(
+                Box::new(Expression::sum(vec![
+                    Expression::product(vec![num.derivative_wrt(variable), *den.clone()]),
+                    Expression::product(vec![
+                        den.derivative_wrt(variable),
+                        *num.clone(),
+                        Expression::constant(-1.0),
+                    ]),
+                ])),
+                Box::new(Expression::product(vec![*den.clone(), *den.clone()])),
+            ),
+            // atan2(y, x)' = (x * y' - y * x') / (x * x + y * y)
+            Self::Atan2(ref y, ref x) => Self::DividedExpressions(
+                Box::new(Expression::sum(vec![
+                    Expression::product(vec![*x.clone(), y.derivative_wrt(variable)]),
+                    Expression::product(vec![
+                        Expression::constant(-1.0),
+                        *y.clone(),
+                        x.derivative_wrt(variable),
+                    ]),
+                ])),
+                Box::new(Expression::sum(vec![
+                    Expression::product(vec![*x.clone(), *x.clone()]),
+                    Expression::product(vec![*y.clone(), *y.clone()]),
+                ])),
+            ),
+            // Differentiated per branch. Where a condition switches the
+            // derivative is reported as undefined (NaN), since the branches
+            // need not meet there.
+            Self::Piecewise(ref branches, ref otherwise) => {
+                let mut derivatives: Vec<(Condition, Expression)> = Vec::new();
+                for (condition, _) in branches {
+                    let boundary = condition.boundary();
+                    if condition.relation != Relation::Equal
+                        && condition.depends_on(variable)
+                        && derivatives.iter().all(|(c, _)| *c != boundary)
+                    {
+                        derivatives.push((boundary, Expression::constant(f64::NAN)));
+                    }
+                }
+                derivatives.extend(
+                    branches.iter().map(|(condition, value)| {
+                        (condition.clone(), value.derivative_wrt(variable))
+                    }),
+                );
+                Self::Piecewise(derivatives, Box::new(otherwise.derivative_wrt(variable)))
+            }
+            Self::Power(ref base, ref exponent) => {
+                match (base.depends_on(variable), exponent.depends_on(variable)) {
+                    // Power rule: (f^c)' = c * f^(c - 1) * f'
+                    (_, false) => {
+                        let c = match exponent.is_constant() {
+                            true => Expression::constant(exponent.evaluate(0.0)),
+                            false => *exponent.clone(),
+                        };
+                        Self::MultipliedExpressions(vec![
+                            c.clone(),
+                            Expression::power(*base.clone(), c - 1.0),
+                            base.derivative_wrt(variable),
+                        ])
+                    }
+                    // (b^g)' = ln(b) * b^g * g'
+                    (false, true) => Self::MultipliedExpressions(vec![
+                        match base.is_constant() {
+                            true => Expression::constant(base.evaluate(0.0).ln()),
+                            false => Expression::apply(FunctionType::Logarithm, *base.clone()),
+                        },
+                        Expression::power(*base.clone(), *exponent.clone()),
+                        exponent.derivative_wrt(variable),
+                    ]),
+                    // (f^g)' = f^g * (g' * ln(f) + g * f' / f)
+                    (true, true) => Self::MultipliedExpressions(vec![
+                        Expression::power(*base.clone(), *exponent.clone()),
+                        Expression::sum(vec![
+                            Expression::product(vec![
+                                exponent.derivative_wrt(variable),
+                                Expression::apply(FunctionType::Logarithm, *base.clone()),
+                            ]),
+                            Expression::quotient(
+                                Expression::product(vec![
+                                    *exponent.clone(),
+                                    base.derivative_wrt(variable),
+                                ]),
+                                *base.clone(),
+                            ),
+                        ]),
+                    ]),
+                }
+            }
+        }
+    }
+}
+
+impl VariableFunction for ExpressionType {
+    type Derivative = Self;
+
+    fn evaluate(&self, x: f64) -> f64 {
+        match &self {
+            Self::Constant(val) => *val,
+            Self::DividedExpressions(num, den) => num.evaluate(x) / den.evaluate(x),
+            Self::Polynomial(ref value) => value.evaluate(x),
+            Self::MultipliedExpressions(ref value) => value.iter().map(|c| c.evaluate(x)).product(),
+            Self::Expressions(ref value) => value.iter().map(|c| c.evaluate(x)).sum(),
+            Self::Power(base, exponent) => base.evaluate(x).powf(exponent.evaluate(x)),
+            Self::Atan2(y, x_) => y.evaluate(x).atan2(x_.evaluate(x)),
+            // Only `x` is bound here, any other variable is NaN, see
+            // `Expression::evaluate_with`.
+            Self::Variable(ref name) if name == "x" => x,
+            Self::Variable(_) => f64::NAN,
+            Self::Piecewise(branches, otherwise) => branches
+                .iter()
+                .find(|(condition, _)| condition.holds(x))
+                .map_or(otherwise.as_ref(), |(_, value)| value)
+                .evaluate(x),
         }
     }
+
+    fn derivative(&self) -> Self {
+        self.derivative_wrt("x")
+    }
 }
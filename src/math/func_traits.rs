@@ -1,5 +1,9 @@
 pub trait VariableFunction {
+    /// Type of the derivative, `Self` for the families that are closed
+    /// under differentiation.
+    type Derivative;
+
     fn evaluate(&self, x: f64) -> f64;
 
-    fn derivative(&self) -> Self;
+    fn derivative(&self) -> Self::Derivative;
 }
@@ -1,41 +1,180 @@
 use std::fmt::Display;
 
+use crate::math::custom::Custom;
+use crate::math::expression::Expression;
 use crate::math::func_traits::VariableFunction;
+use crate::math::hyperbolic::{HyperbolicFunction, InverseHyperbolicFunction};
+use crate::math::inverse_trigonometric::InverseTrigonometricFunction;
 use crate::math::polynomial::Polynomial;
+use crate::math::render::Renderer;
+use crate::math::special::SpecialFunction;
 use crate::math::trigonometric::TrigonometricFunction;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FunctionType {
     Trigonometric(TrigonometricFunction),
+    InverseTrigonometric(InverseTrigonometricFunction),
+    Hyperbolic(HyperbolicFunction),
+    InverseHyperbolic(InverseHyperbolicFunction),
+    Special(SpecialFunction),
+    /// A user supplied function, see [`crate::math::custom::Registry`].
+    Custom(Custom),
     None,
     Polynomial(Polynomial<f64>),
+    /// Natural logarithm.
+    Logarithm,
+    /// Logarithm to the given base.
+    LogarithmBase(f64),
+    Exponential,
+    SquareRoot,
+    /// Raises the input to a constant power, `x^r` for any real `r`.
+    Power(f64),
+    /// Function given by an expression in `x`, applying it substitutes the
+    /// input for `x`. Used for derivatives that leave the other families,
+    /// such as `0.5 x^(-0.5)` for the square root.
+    Lambda(Box<Expression>),
 }
 
 impl Display for FunctionType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let output = match &self {
-            Self::None => "".to_string(),
-            Self::Polynomial(ref value) => value.to_string(),
-            Self::Trigonometric(ref value) => value.to_string(),
-        };
-        f.write_str(output.as_str())
+        f.write_str(&Renderer::plain(f.precision()).function(self))
+    }
+}
+
+impl FunctionType {
+    /// Applies the function to `argument`.
+    pub fn of(self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(self, argument.into())
+    }
+
+    /// Whether `x` lies in the domain, outside of it `evaluate` is NaN or
+    /// infinite.
+    pub fn domain_contains(&self, x: f64) -> bool {
+        match *self {
+            FunctionType::InverseTrigonometric(ref value) => value.domain_contains(x),
+            FunctionType::InverseHyperbolic(ref value) => value.domain_contains(x),
+            FunctionType::Special(ref value) => value.domain_contains(x),
+            FunctionType::Logarithm | FunctionType::LogarithmBase(_) => x > 0.0,
+            FunctionType::SquareRoot => x >= 0.0,
+            FunctionType::Power(r) => {
+                (r.fract() == 0.0 || x >= 0.0) && (r >= 0.0 || x != 0.0) && !x.is_nan()
+            }
+            _ => self.evaluate(x).is_finite(),
+        }
+    }
+
+    /// The inverse function on the principal branch, where one is known.
+    pub fn inverse(&self) -> Option<FunctionType> {
+        use HyperbolicFunction as H;
+        use InverseHyperbolicFunction as IH;
+        use InverseTrigonometricFunction as IT;
+        use TrigonometricFunction as T;
+
+        let x = Expression::variable("x");
+        let pairs = [
+            (T::Sine, IT::Arcsine),
+            (T::Cosine, IT::Arccosine),
+            (T::Tangent, IT::Arctangent),
+            (T::Cotangent, IT::Arccotangent),
+            (T::Secant, IT::Arcsecant),
+            (T::Cosecant, IT::Arccosecant),
+        ];
+        let hyperbolic_pairs = [
+            (H::Sine, IH::Arsine),
+            (H::Cosine, IH::Arcosine),
+            (H::Tangent, IH::Artangent),
+            (H::Cotangent, IH::Arcotangent),
+            (H::Secant, IH::Arsecant),
+            (H::Cosecant, IH::Arcosecant),
+        ];
+        Some(match *self {
+            FunctionType::None => FunctionType::None,
+            FunctionType::Exponential => FunctionType::Logarithm,
+            FunctionType::Logarithm => FunctionType::Exponential,
+            FunctionType::LogarithmBase(base) => {
+                FunctionType::Lambda(Box::new(Expression::power(Expression::constant(base), x)))
+            }
+            FunctionType::SquareRoot => FunctionType::Power(2.0),
+            FunctionType::Power(r) if r != 0.0 => FunctionType::Power(r.recip()),
+            FunctionType::Trigonometric(ref value) => {
+                FunctionType::InverseTrigonometric(pairs.iter().find(|(f, _)| f == value)?.1)
+            }
+            FunctionType::InverseTrigonometric(value) => {
+                FunctionType::Trigonometric(pairs.into_iter().find(|(_, f)| *f == value)?.0)
+            }
+            FunctionType::Hyperbolic(value) => FunctionType::InverseHyperbolic(
+                hyperbolic_pairs.iter().find(|(f, _)| *f == value)?.1,
+            ),
+            FunctionType::InverseHyperbolic(value) => {
+                FunctionType::Hyperbolic(hyperbolic_pairs.iter().find(|(_, f)| *f == value)?.0)
+            }
+            FunctionType::Special(SpecialFunction::LambertW) => {
+                FunctionType::Lambda(Box::new(&x * FunctionType::Exponential.of(x.clone())))
+            }
+            FunctionType::Custom(ref value) => value.inverse()?,
+            _ => return None,
+        })
     }
 }
 
 impl VariableFunction for FunctionType {
+    type Derivative = Self;
+
     fn evaluate(&self, x: f64) -> f64 {
         match *self {
             FunctionType::Trigonometric(ref trig) => trig.evaluate(x),
+            FunctionType::InverseTrigonometric(ref value) => value.evaluate(x),
+            FunctionType::Hyperbolic(ref value) => value.evaluate(x),
+            FunctionType::InverseHyperbolic(ref value) => value.evaluate(x),
+            FunctionType::Special(ref value) => value.evaluate(x),
+            FunctionType::Custom(ref value) => value.evaluate(x),
             FunctionType::Polynomial(ref poly) => poly.evaluate(x),
-            _ => x,
+            FunctionType::Logarithm => x.ln(),
+            FunctionType::LogarithmBase(base) => x.log(base),
+            FunctionType::Exponential => x.exp(),
+            FunctionType::SquareRoot => x.sqrt(),
+            FunctionType::Power(r) => x.powf(r),
+            FunctionType::Lambda(ref expr) => expr.evaluate(x),
+            FunctionType::None => x,
         }
     }
 
     fn derivative(&self) -> Self {
+        let x = || Expression::variable("x");
         match *self {
             FunctionType::Trigonometric(ref trig) => FunctionType::Trigonometric(trig.derivative()),
+            FunctionType::InverseTrigonometric(ref value) => value.derivative(),
+            FunctionType::Hyperbolic(ref value) => value.derivative(),
+            FunctionType::InverseHyperbolic(ref value) => value.derivative(),
+            FunctionType::Special(ref value) => value.derivative(),
+            FunctionType::Custom(ref value) => value.derivative(),
             FunctionType::Polynomial(ref poly) => FunctionType::Polynomial(poly.derivative()),
-            _ => FunctionType::None,
+            FunctionType::Logarithm => FunctionType::Power(-1.0),
+            FunctionType::LogarithmBase(base) => FunctionType::Lambda(Box::new(
+                base.ln().recip() * FunctionType::Power(-1.0).of(x()),
+            )),
+            FunctionType::Exponential => FunctionType::Exponential,
+            FunctionType::SquareRoot => {
+                FunctionType::Lambda(Box::new(0.5 * FunctionType::Power(-0.5).of(x())))
+            }
+            FunctionType::Power(0.0) => FunctionType::Polynomial(Polynomial {
+                poly: vec![0.0],
+                deg: 0,
+            }),
+            FunctionType::Power(1.0) => FunctionType::None.derivative(),
+            FunctionType::Power(2.0) => FunctionType::Polynomial(Polynomial {
+                poly: vec![0.0, 2.0],
+                deg: 1,
+            }),
+            FunctionType::Power(r) => {
+                FunctionType::Lambda(Box::new(r * FunctionType::Power(r - 1.0).of(x())))
+            }
+            FunctionType::Lambda(ref expr) => FunctionType::Lambda(Box::new(expr.derivative())),
+            // The identity has the constant one as its derivative.
+            FunctionType::None => FunctionType::Polynomial(Polynomial {
+                poly: vec![1.0],
+                deg: 0,
+            }),
         }
     }
 }
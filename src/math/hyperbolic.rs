@@ -0,0 +1,218 @@
+use std::fmt::Display;
+
+use crate::math::{
+    expression::Expression, func_traits::VariableFunction, function_type::FunctionType,
+    render::Renderer,
+};
+
+/// Hyperbolic functions. Their derivatives leave the family (`sech^2` for
+/// `tanh`), so they are [`FunctionType::Lambda`] expressions in `x`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HyperbolicFunction {
+    Sine,
+    Cosine,
+    Tangent,
+    Cotangent,
+    Secant,
+    Cosecant,
+}
+
+/// Inverses of the hyperbolic functions, on the principal branch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InverseHyperbolicFunction {
+    Arsine,
+    /// Takes values in `[0, inf)`.
+    Arcosine,
+    Artangent,
+    Arcotangent,
+    /// Takes values in `[0, inf)`.
+    Arsecant,
+    Arcosecant,
+}
+
+#[macro_export]
+macro_rules! sinh {
+    () => {
+        $crate::math::hyperbolic::HyperbolicFunction::Sine
+    };
+}
+
+#[macro_export]
+macro_rules! cosh {
+    () => {
+        $crate::math::hyperbolic::HyperbolicFunction::Cosine
+    };
+}
+
+#[macro_export]
+macro_rules! tanh {
+    () => {
+        $crate::math::hyperbolic::HyperbolicFunction::Tangent
+    };
+}
+
+#[macro_export]
+macro_rules! coth {
+    () => {
+        $crate::math::hyperbolic::HyperbolicFunction::Cotangent
+    };
+}
+
+#[macro_export]
+macro_rules! sech {
+    () => {
+        $crate::math::hyperbolic::HyperbolicFunction::Secant
+    };
+}
+
+#[macro_export]
+macro_rules! csch {
+    () => {
+        $crate::math::hyperbolic::HyperbolicFunction::Cosecant
+    };
+}
+
+#[macro_export]
+macro_rules! asinh {
+    () => {
+        $crate::math::hyperbolic::InverseHyperbolicFunction::Arsine
+    };
+}
+
+#[macro_export]
+macro_rules! acosh {
+    () => {
+        $crate::math::hyperbolic::InverseHyperbolicFunction::Arcosine
+    };
+}
+
+#[macro_export]
+macro_rules! atanh {
+    () => {
+        $crate::math::hyperbolic::InverseHyperbolicFunction::Artangent
+    };
+}
+
+#[macro_export]
+macro_rules! acoth {
+    () => {
+        $crate::math::hyperbolic::InverseHyperbolicFunction::Arcotangent
+    };
+}
+
+#[macro_export]
+macro_rules! asech {
+    () => {
+        $crate::math::hyperbolic::InverseHyperbolicFunction::Arsecant
+    };
+}
+
+#[macro_export]
+macro_rules! acsch {
+    () => {
+        $crate::math::hyperbolic::InverseHyperbolicFunction::Arcosecant
+    };
+}
+
+impl Display for HyperbolicFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(Renderer::plain(None).hyperbolic(self))
+    }
+}
+
+impl HyperbolicFunction {
+    /// Applies the function to `argument`, e.g. `tanh!().of(x!())`.
+    pub fn of(self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(FunctionType::Hyperbolic(self), argument.into())
+    }
+}
+
+impl VariableFunction for HyperbolicFunction {
+    type Derivative = FunctionType;
+
+    fn evaluate(&self, x: f64) -> f64 {
+        match self {
+            Self::Sine => x.sinh(),
+            Self::Cosine => x.cosh(),
+            Self::Tangent => x.tanh(),
+            Self::Cotangent => x.tanh().recip(),
+            Self::Secant => x.cosh().recip(),
+            Self::Cosecant => x.sinh().recip(),
+        }
+    }
+
+    fn derivative(&self) -> FunctionType {
+        let x = || Expression::variable("x");
+        let square = |function: HyperbolicFunction| {
+            Expression::power(function.of(x()), Expression::constant(2.0))
+        };
+        FunctionType::Lambda(Box::new(match self {
+            Self::Sine => Self::Cosine.of(x()),
+            Self::Cosine => Self::Sine.of(x()),
+            Self::Tangent => square(Self::Secant),
+            Self::Cotangent => -square(Self::Cosecant),
+            Self::Secant => -(Self::Secant.of(x()) * Self::Tangent.of(x())),
+            Self::Cosecant => -(Self::Cosecant.of(x()) * Self::Cotangent.of(x())),
+        }))
+    }
+}
+
+impl Display for InverseHyperbolicFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(Renderer::plain(None).inverse_hyperbolic(self))
+    }
+}
+
+impl InverseHyperbolicFunction {
+    /// Applies the function to `argument`, e.g. `asinh!().of(x!())`.
+    pub fn of(self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(FunctionType::InverseHyperbolic(self), argument.into())
+    }
+
+    /// Whether `x` lies in the domain, outside of it `evaluate` is NaN or
+    /// infinite.
+    pub fn domain_contains(&self, x: f64) -> bool {
+        match self {
+            Self::Arsine => !x.is_nan(),
+            Self::Arcosine => x >= 1.0,
+            Self::Artangent => x.abs() < 1.0,
+            Self::Arcotangent => x.abs() > 1.0,
+            Self::Arsecant => x > 0.0 && x <= 1.0,
+            Self::Arcosecant => x != 0.0 && !x.is_nan(),
+        }
+    }
+}
+
+impl VariableFunction for InverseHyperbolicFunction {
+    type Derivative = FunctionType;
+
+    fn evaluate(&self, x: f64) -> f64 {
+        match self {
+            Self::Arsine => x.asinh(),
+            Self::Arcosine => x.acosh(),
+            Self::Artangent => x.atanh(),
+            Self::Arcotangent => x.recip().atanh(),
+            Self::Arsecant => x.recip().acosh(),
+            Self::Arcosecant => x.recip().asinh(),
+        }
+    }
+
+    /// The derivative as a lambda, e.g. `(x^2 + 1)^(-0.5)` for the area
+    /// sine.
+    fn derivative(&self) -> FunctionType {
+        let x = Expression::variable("x");
+        let power = |base: Expression, exponent: f64| {
+            Expression::power(base, Expression::constant(exponent))
+        };
+        FunctionType::Lambda(Box::new(match self {
+            Self::Arsine => power(&x * &x + 1.0, -0.5),
+            Self::Arcosine => power(&x * &x - 1.0, -0.5),
+            Self::Artangent | Self::Arcotangent => power(1.0 - &x * &x, -1.0),
+            Self::Arsecant => -(power(x.clone(), -1.0) * power(1.0 - &x * &x, -0.5)),
+            // |x| sqrt(1 + x^2) = x^2 sqrt(1 + x^(-2)), which avoids |x|.
+            Self::Arcosecant => {
+                -(power(x.clone(), -2.0) * power(power(x.clone(), -2.0) + 1.0, -0.5))
+            }
+        }))
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::Display;
+
+use crate::math::func_traits::VariableFunction;
+
+/// Result of a numerical integration, `error` is an estimate of the
+/// absolute error of `value`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quadrature {
+    pub value: f64,
+    pub error: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuadratureError {
+    /// One of the bounds is NaN.
+    InvalidInterval,
+    /// The integrand produced a NaN or infinite sample.
+    NonFinite,
+    /// The adaptive rule ran out of subdivisions before reaching the
+    /// requested tolerance, the best estimate is still reported.
+    NotConverged(Quadrature),
+}
+
+impl Display for QuadratureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidInterval => f.write_str("integration bounds must not be NaN"),
+            Self::NonFinite => f.write_str("integrand is not finite on the interval"),
+            Self::NotConverged(result) => write!(
+                f,
+                "integration did not converge, best estimate {} with error {}",
+                result.value, result.error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QuadratureError {}
+
+/// Maps `[a, b]` onto a finite interval when either bound is infinite.
+///
+/// The substitutions are `x = a + t / (1 - t)` on `[0, 1)`,
+/// `x = b - (1 - t) / t` on `(0, 1]` and `x = t / (1 - t^2)` on `(-1, 1)`.
+/// The transformed integrand is taken to vanish at the singular endpoint,
+/// which holds whenever the original integral converges.
+fn transform<'a, F: VariableFunction>(
+    f: &'a F,
+    a: f64,
+    b: f64,
+) -> (Box<dyn Fn(f64) -> f64 + 'a>, f64, f64) {
+    match (a.is_infinite(), b.is_infinite()) {
+        (false, false) => (Box::new(move |x| f.evaluate(x)), a, b),
+        (false, true) => (
+            Box::new(move |t: f64| match 1.0 - t {
+                s if s <= 0.0 => 0.0,
+                s => f.evaluate(a + t / s) / (s * s),
+            }),
+            0.0,
+            1.0,
+        ),
+        (true, false) => (
+            Box::new(move |t: f64| match t {
+                t if t <= 0.0 => 0.0,
+                t => f.evaluate(b - (1.0 - t) / t) / (t * t),
+            }),
+            0.0,
+            1.0,
+        ),
+        (true, true) => (
+            Box::new(move |t: f64| match 1.0 - t * t {
+                s if s <= 0.0 => 0.0,
+                s => f.evaluate(t / s) * (1.0 + t * t) / (s * s),
+            }),
+            -1.0,
+            1.0,
+        ),
+    }
+}
+
+/// Shared handling of reversed, empty and NaN intervals, `rule` only ever
+/// sees `a < b` with finite bounds.
+fn integrate<F, R>(f: &F, a: f64, b: f64, rule: R) -> Result<Quadrature, QuadratureError>
+where
+    F: VariableFunction,
+    R: Fn(&dyn Fn(f64) -> f64, f64, f64) -> Result<Quadrature, QuadratureError>,
+{
+    if a.is_nan() || b.is_nan() {
+        return Err(QuadratureError::InvalidInterval);
+    }
+    if a == b {
+        return Ok(Quadrature {
+            value: 0.0,
+            error: 0.0,
+        });
+    }
+    if a > b {
+        return integrate(f, b, a, rule).map(|result| Quadrature {
+            value: -result.value,
+            error: result.error,
+        });
+    }
+
+    let (g, lower, upper) = transform(f, a, b);
+    let result = rule(&g, lower, upper)?;
+    if result.value.is_finite() {
+        Ok(result)
+    } else {
+        Err(QuadratureError::NonFinite)
+    }
+}
+
+fn simpson_sum(g: &dyn Fn(f64) -> f64, a: f64, b: f64, intervals: usize) -> f64 {
+    let h = (b - a) / intervals as f64;
+    let inner = (1..intervals)
+        .map(|i| match i % 2 {
+            0 => 2.0 * g(a + i as f64 * h),
+            _ => 4.0 * g(a + i as f64 * h),
+        })
+        .sum::<f64>();
+    (g(a) + inner + g(b)) * h / 3.0
+}
+
+/// Composite Simpson rule on `2 * intervals` sub-intervals (`intervals` is
+/// rounded up to an even count), the error is estimated by Richardson
+/// extrapolation against the rule on `intervals` sub-intervals.
+pub fn simpson<F: VariableFunction>(
+    f: &F,
+    a: f64,
+    b: f64,
+    intervals: usize,
+) -> Result<Quadrature, QuadratureError> {
+    let intervals = intervals.max(2).div_ceil(2) * 2;
+    integrate(f, a, b, |g, a, b| {
+        let coarse = simpson_sum(g, a, b, intervals);
+        let fine = simpson_sum(g, a, b, 2 * intervals);
+        Ok(Quadrature {
+            value: fine,
+            error: (fine - coarse).abs() / 15.0,
+        })
+    })
+}
+
+/// Nodes and weights of the `order`-point Gauss–Legendre rule on
+/// `[-1, 1]`, found by Newton iteration on the Legendre polynomial.
+fn legendre_nodes(order: usize) -> Vec<(f64, f64)> {
+    let n = order as f64;
+    (0..order)
+        .map(|i| {
+            let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n + 0.5)).cos();
+            let mut derivative = 1.0;
+            for _ in 0..100 {
+                // Three term recurrence for P_n(x) and P_{n-1}(x).
+                let (mut p, mut previous) = (x, 1.0);
+                for k in 2..=order {
+                    let k = k as f64;
+                    (p, previous) = (((2.0 * k - 1.0) * x * p - (k - 1.0) * previous) / k, p);
+                }
+                derivative = n * (x * p - previous) / (x * x - 1.0);
+                let step = p / derivative;
+                x -= step;
+                if step.abs() < 1e-15 {
+                    break;
+                }
+            }
+            (x, 2.0 / ((1.0 - x * x) * derivative * derivative))
+        })
+        .collect()
+}
+
+fn gauss_legendre_sum(g: &dyn Fn(f64) -> f64, a: f64, b: f64, order: usize) -> f64 {
+    let (mid, half) = ((a + b) / 2.0, (b - a) / 2.0);
+    legendre_nodes(order)
+        .iter()
+        .map(|(x, w)| w * g(mid + half * x))
+        .sum::<f64>()
+        * half
+}
+
+/// Gauss–Legendre rule with `order` nodes, the error is estimated from the
+/// rule with one more node.
+pub fn gauss_legendre<F: VariableFunction>(
+    f: &F,
+    a: f64,
+    b: f64,
+    order: usize,
+) -> Result<Quadrature, QuadratureError> {
+    let order = order.max(1);
+    integrate(f, a, b, |g, a, b| {
+        let value = gauss_legendre_sum(g, a, b, order);
+        Ok(Quadrature {
+            value,
+            error: (value - gauss_legendre_sum(g, a, b, order + 1)).abs(),
+        })
+    })
+}
+
+/// Non-negative abscissae of the 15-point Kronrod rule on `[-1, 1]`, the
+/// odd entries are shared with the embedded 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.0,
+];
+
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_22,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_18,
+    0.140_653_259_715_525_92,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_83,
+];
+
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+
+/// Applies the G7–K15 pair to `[a, b]`.
+fn gauss_kronrod_15(g: &dyn Fn(f64) -> f64, a: f64, b: f64) -> Quadrature {
+    let (mid, half) = ((a + b) / 2.0, (b - a) / 2.0);
+    let centre = g(mid);
+    let mut kronrod = centre * KRONROD_WEIGHTS[7];
+    let mut gauss = centre * GAUSS_WEIGHTS[3];
+    for (index, (x, w)) in KRONROD_NODES[..7]
+        .iter()
+        .zip(KRONROD_WEIGHTS[..7].iter())
+        .enumerate()
+    {
+        let pair = g(mid - half * x) + g(mid + half * x);
+        kronrod += w * pair;
+        if index % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[index / 2] * pair;
+        }
+    }
+    Quadrature {
+        value: kronrod * half,
+        error: ((kronrod - gauss) * half).abs(),
+    }
+}
+
+/// Adaptive Gauss–Kronrod (G7–K15) integration, bisecting the interval
+/// with the largest error estimate until the total estimate drops below
+/// `tolerance` or `max_intervals` sub-intervals are in use.
+pub fn gauss_kronrod<F: VariableFunction>(
+    f: &F,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+    max_intervals: usize,
+) -> Result<Quadrature, QuadratureError> {
+    integrate(f, a, b, |g, a, b| {
+        let mut intervals = vec![(a, b, gauss_kronrod_15(g, a, b))];
+        loop {
+            let total = intervals.iter().fold(
+                Quadrature {
+                    value: 0.0,
+                    error: 0.0,
+                },
+                |total, (_, _, result)| Quadrature {
+                    value: total.value + result.value,
+                    error: total.error + result.error,
+                },
+            );
+            if !total.value.is_finite() || !total.error.is_finite() {
+                return Err(QuadratureError::NonFinite);
+            }
+            if total.error <= tolerance {
+                return Ok(total);
+            }
+            if intervals.len() >= max_intervals.max(1) {
+                return Err(QuadratureError::NotConverged(total));
+            }
+
+            let (worst, _) = intervals
+                .iter()
+                .enumerate()
+                .max_by(|(_, l), (_, r)| l.2.error.total_cmp(&r.2.error))
+                .unwrap();
+            let (lower, upper, _) = intervals.swap_remove(worst);
+            let mid = (lower + upper) / 2.0;
+            intervals.push((lower, mid, gauss_kronrod_15(g, lower, mid)));
+            intervals.push((mid, upper, gauss_kronrod_15(g, mid, upper)));
+        }
+    })
+}
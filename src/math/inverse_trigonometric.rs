@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+use crate::math::{
+    expression::Expression, func_traits::VariableFunction, function_type::FunctionType,
+    render::Renderer,
+};
+
+/// Inverse of a trigonometric function, on the principal branch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InverseTrigonometricFunction {
+    Arcsine,
+    Arccosine,
+    Arctangent,
+    /// Takes values in `(0, pi)`.
+    Arccotangent,
+    Arcsecant,
+    Arccosecant,
+}
+
+#[macro_export]
+macro_rules! asin {
+    () => {
+        $crate::math::inverse_trigonometric::InverseTrigonometricFunction::Arcsine
+    };
+}
+
+#[macro_export]
+macro_rules! acos {
+    () => {
+        $crate::math::inverse_trigonometric::InverseTrigonometricFunction::Arccosine
+    };
+}
+
+#[macro_export]
+macro_rules! atan {
+    () => {
+        $crate::math::inverse_trigonometric::InverseTrigonometricFunction::Arctangent
+    };
+}
+
+#[macro_export]
+macro_rules! acot {
+    () => {
+        $crate::math::inverse_trigonometric::InverseTrigonometricFunction::Arccotangent
+    };
+}
+
+#[macro_export]
+macro_rules! asec {
+    () => {
+        $crate::math::inverse_trigonometric::InverseTrigonometricFunction::Arcsecant
+    };
+}
+
+#[macro_export]
+macro_rules! acsc {
+    () => {
+        $crate::math::inverse_trigonometric::InverseTrigonometricFunction::Arccosecant
+    };
+}
+
+impl Display for InverseTrigonometricFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(Renderer::plain(None).inverse_trigonometric(self))
+    }
+}
+
+impl InverseTrigonometricFunction {
+    /// Applies the function to `argument`, e.g. `asin!().of(x!())`.
+    pub fn of(self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(FunctionType::InverseTrigonometric(self), argument.into())
+    }
+
+    /// Whether `x` lies in the domain, outside of it `evaluate` is NaN.
+    pub fn domain_contains(&self, x: f64) -> bool {
+        match self {
+            Self::Arcsine | Self::Arccosine => (-1.0..=1.0).contains(&x),
+            Self::Arctangent | Self::Arccotangent => !x.is_nan(),
+            Self::Arcsecant | Self::Arccosecant => x.abs() >= 1.0,
+        }
+    }
+}
+
+impl VariableFunction for InverseTrigonometricFunction {
+    type Derivative = FunctionType;
+
+    fn evaluate(&self, x: f64) -> f64 {
+        match self {
+            Self::Arcsine => x.asin(),
+            Self::Arccosine => x.acos(),
+            Self::Arctangent => x.atan(),
+            Self::Arccotangent => std::f64::consts::FRAC_PI_2 - x.atan(),
+            Self::Arcsecant => x.recip().acos(),
+            Self::Arccosecant => x.recip().asin(),
+        }
+    }
+
+    /// The derivative as a lambda, these are algebraic rather than
+    /// trigonometric, e.g. `(1 - x^2)^(-0.5)` for the arcsine.
+    fn derivative(&self) -> FunctionType {
+        let x = Expression::variable("x");
+        let root = |radicand: Expression| Expression::power(radicand, Expression::constant(-0.5));
+        // |x| sqrt(x^2 - 1) = x^2 sqrt(1 - x^(-2)), which avoids |x|.
+        let secant = || {
+            Expression::product(vec![
+                Expression::power(x.clone(), Expression::constant(-2.0)),
+                root(1.0 - Expression::power(x.clone(), Expression::constant(-2.0))),
+            ])
+        };
+        FunctionType::Lambda(Box::new(match self {
+            Self::Arcsine => root(1.0 - &x * &x),
+            Self::Arccosine => -root(1.0 - &x * &x),
+            Self::Arctangent => Expression::power(&x * &x + 1.0, Expression::constant(-1.0)),
+            Self::Arccotangent => -Expression::power(&x * &x + 1.0, Expression::constant(-1.0)),
+            Self::Arcsecant => secant(),
+            Self::Arccosecant => -secant(),
+        }))
+    }
+}
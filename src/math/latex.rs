@@ -0,0 +1,46 @@
+use crate::math::{
+    expression::Expression,
+    expression_type::ExpressionType,
+    function_type::FunctionType,
+    polynomial::{Polynomial, PolynomialOperationTypes},
+    render::{DisplayCoefficient, Renderer},
+    trigonometric::TrigonometricFunction,
+};
+
+/// Rendering of mathematical objects as LaTeX source.
+pub trait ToLatex {
+    fn to_latex(&self) -> String;
+}
+
+impl ToLatex for Expression {
+    fn to_latex(&self) -> String {
+        Renderer::latex().expression(self).0
+    }
+}
+
+impl ToLatex for ExpressionType {
+    fn to_latex(&self) -> String {
+        Renderer::latex().input(self).0
+    }
+}
+
+impl ToLatex for FunctionType {
+    fn to_latex(&self) -> String {
+        Renderer::latex().function(self)
+    }
+}
+
+impl ToLatex for TrigonometricFunction {
+    fn to_latex(&self) -> String {
+        Renderer::latex().trigonometric(self, None).0
+    }
+}
+
+impl<T> ToLatex for Polynomial<T>
+where
+    T: PolynomialOperationTypes + DisplayCoefficient,
+{
+    fn to_latex(&self) -> String {
+        Renderer::latex().polynomial(self, "x").0
+    }
+}
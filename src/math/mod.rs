@@ -1,6 +1,22 @@
+pub mod antiderivative;
+pub mod closed_form;
+pub mod complex;
+pub mod custom;
 pub mod expression;
 pub mod expression_type;
 pub mod func_traits;
 pub mod function_type;
+pub mod hyperbolic;
+pub mod integration;
+pub mod inverse_trigonometric;
+pub mod latex;
+pub mod operators;
+pub mod parser;
+pub mod piecewise;
 pub mod polynomial;
+pub mod render;
+pub mod roots;
+pub mod special;
+pub mod sturm;
 pub mod trigonometric;
+pub mod variable;
@@ -0,0 +1,176 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::math::{
+    expression::Expression, expression_type::ExpressionType, function_type::FunctionType,
+    polynomial::Polynomial,
+};
+
+impl From<f64> for Expression {
+    fn from(value: f64) -> Self {
+        Expression::constant(value)
+    }
+}
+
+impl From<Polynomial<f64>> for Expression {
+    fn from(value: Polynomial<f64>) -> Self {
+        Expression::polynomial(value)
+    }
+}
+
+/// Splits a bare sum into its terms, or a bare product into its factors.
+fn operands(expr: Expression, product: bool) -> Vec<Expression> {
+    match (product, expr) {
+        (
+            false,
+            Expression {
+                function: FunctionType::None,
+                input: ExpressionType::Expressions(terms),
+            },
+        ) => terms,
+        (
+            true,
+            Expression {
+                function: FunctionType::None,
+                input: ExpressionType::MultipliedExpressions(factors),
+            },
+        ) => factors,
+        (_, other) => vec![other],
+    }
+}
+
+/// Appends `rhs` to the operands of `lhs`, folding polynomial operands
+/// into one already present with `merge`.
+fn combine(
+    lhs: Expression,
+    rhs: Expression,
+    product: bool,
+    merge: fn(Polynomial<f64>, Polynomial<f64>) -> Polynomial<f64>,
+) -> Vec<Expression> {
+    let mut result = operands(lhs, product);
+    for operand in operands(rhs, product) {
+        match operand.as_polynomial().and_then(|r| {
+            result
+                .iter()
+                .position(|t| t.as_polynomial().is_some())
+                .map(|position| (position, r))
+        }) {
+            Some((position, r)) => {
+                let l = result[position].as_polynomial().unwrap();
+                result[position] = Expression::polynomial(merge(l, r));
+            }
+            None => result.push(operand),
+        }
+    }
+    result
+}
+
+fn add(lhs: Expression, rhs: Expression) -> Expression {
+    if let (Some(l), Some(r)) = (lhs.as_polynomial(), rhs.as_polynomial()) {
+        return Expression::polynomial(l + r);
+    }
+    Expression::sum(combine(lhs, rhs, false, |l, r| l + r))
+}
+
+fn multiply(lhs: Expression, rhs: Expression) -> Expression {
+    if let (Some(l), Some(r)) = (lhs.as_polynomial(), rhs.as_polynomial()) {
+        return Expression::polynomial(l * &r);
+    }
+    Expression::product(combine(lhs, rhs, true, |l, r| l * &r))
+}
+
+fn divide(lhs: Expression, rhs: Expression) -> Expression {
+    if let (Some(l), Some(r)) = (lhs.as_polynomial(), rhs.as_constant()) {
+        if r != 0.0 {
+            return Expression::polynomial(l / r);
+        }
+    }
+    Expression::quotient(lhs, rhs)
+}
+
+fn negate(expr: Expression) -> Expression {
+    multiply(Expression::constant(-1.0), expr)
+}
+
+fn subtract(lhs: Expression, rhs: Expression) -> Expression {
+    add(lhs, negate(rhs))
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        negate(self)
+    }
+}
+
+impl Neg for &Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        negate(self.clone())
+    }
+}
+
+/// Implements a binary operator for owned and borrowed expressions, with
+/// anything convertible into an expression (`f64`, `Polynomial<f64>`) on
+/// the right and `f64` or `Polynomial<f64>` on the left.
+macro_rules! define_operator {
+    ($trait: ident, $method: ident, $function: ident) => {
+        impl<R: Into<Expression>> $trait<R> for Expression {
+            type Output = Expression;
+
+            fn $method(self, rhs: R) -> Expression {
+                $function(self, rhs.into())
+            }
+        }
+
+        impl<R: Into<Expression>> $trait<R> for &Expression {
+            type Output = Expression;
+
+            fn $method(self, rhs: R) -> Expression {
+                $function(self.clone(), rhs.into())
+            }
+        }
+
+        impl $trait<&Expression> for Expression {
+            type Output = Expression;
+
+            fn $method(self, rhs: &Expression) -> Expression {
+                $function(self, rhs.clone())
+            }
+        }
+
+        impl $trait<&Expression> for &Expression {
+            type Output = Expression;
+
+            fn $method(self, rhs: &Expression) -> Expression {
+                $function(self.clone(), rhs.clone())
+            }
+        }
+
+        define_operator!($trait, $method, $function, f64);
+        define_operator!($trait, $method, $function, Polynomial<f64>);
+    };
+    ($trait: ident, $method: ident, $function: ident, $lhs: ty) => {
+        impl $trait<Expression> for $lhs {
+            type Output = Expression;
+
+            fn $method(self, rhs: Expression) -> Expression {
+                $function(self.into(), rhs)
+            }
+        }
+
+        impl $trait<&Expression> for $lhs {
+            type Output = Expression;
+
+            fn $method(self, rhs: &Expression) -> Expression {
+                $function(self.into(), rhs.clone())
+            }
+        }
+    };
+}
+
+define_operator!(Add, add, add);
+define_operator!(Sub, sub, subtract);
+define_operator!(Mul, mul, multiply);
+define_operator!(Div, div, divide);
@@ -0,0 +1,561 @@
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::math::{
+    custom::Registry,
+    expression::Expression,
+    func_traits::VariableFunction,
+    function_type::FunctionType,
+    hyperbolic::{HyperbolicFunction, InverseHyperbolicFunction},
+    inverse_trigonometric::InverseTrigonometricFunction,
+    piecewise::{Condition, Relation},
+    polynomial::MAX_EXPANDED_DEGREE,
+    special::SpecialFunction,
+    trigonometric::TrigonometricFunction,
+};
+
+/// Error produced while parsing an expression, `span` is the byte range
+/// of the offending input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(span: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Comma,
+    Semicolon,
+    Relation(Relation),
+    End,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "number `{}`", value),
+            Self::Ident(name) => write!(f, "`{}`", name),
+            Self::Plus => f.write_str("`+`"),
+            Self::Minus => f.write_str("`-`"),
+            Self::Star => f.write_str("`*`"),
+            Self::Slash => f.write_str("`/`"),
+            Self::Caret => f.write_str("`^`"),
+            Self::LParen => f.write_str("`(`"),
+            Self::RParen => f.write_str("`)`"),
+            Self::LBrace => f.write_str("`{`"),
+            Self::RBrace => f.write_str("`}`"),
+            Self::Comma => f.write_str("`,`"),
+            Self::Semicolon => f.write_str("`;`"),
+            Self::Relation(relation) => f.write_str(match relation {
+                Relation::Less => "`<`",
+                Relation::LessEqual => "`<=`",
+                Relation::Greater => "`>`",
+                Relation::GreaterEqual => "`>=`",
+                Relation::Equal => "`=`",
+                Relation::NotEqual => "`!=`",
+            }),
+            Self::End => f.write_str("end of input"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, Range<usize>)>, ParseError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+        let ch = bytes[index];
+        let token = match ch {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                index += 1;
+                continue;
+            }
+            b'+' => Token::Plus,
+            b'-' => Token::Minus,
+            b'*' => Token::Star,
+            b'/' => Token::Slash,
+            b'^' => Token::Caret,
+            b'(' => Token::LParen,
+            b')' => Token::RParen,
+            b'{' => Token::LBrace,
+            b'}' => Token::RBrace,
+            b',' => Token::Comma,
+            b';' => Token::Semicolon,
+            b'<' | b'>' | b'=' | b'!' => {
+                let equals = bytes.get(index + 1) == Some(&b'=');
+                let relation = match (ch, equals) {
+                    (b'<', false) => Relation::Less,
+                    (b'<', true) => Relation::LessEqual,
+                    (b'>', false) => Relation::Greater,
+                    (b'>', true) => Relation::GreaterEqual,
+                    (b'=', _) => Relation::Equal,
+                    (b'!', true) => Relation::NotEqual,
+                    _ => {
+                        return Err(ParseError::new(
+                            start..start + 1,
+                            "unexpected character `!`",
+                        ))
+                    }
+                };
+                // `==` is accepted as well as `=`.
+                if equals {
+                    index += 1;
+                }
+                Token::Relation(relation)
+            }
+            b'0'..=b'9' | b'.' => {
+                while index < bytes.len() && (bytes[index].is_ascii_digit() || bytes[index] == b'.')
+                {
+                    index += 1;
+                }
+                // Scientific notation, only when an exponent actually follows,
+                // so that `2e` still reads as `2 * e`.
+                if index < bytes.len() && (bytes[index] == b'e' || bytes[index] == b'E') {
+                    let mut lookahead = index + 1;
+                    if lookahead < bytes.len()
+                        && (bytes[lookahead] == b'+' || bytes[lookahead] == b'-')
+                    {
+                        lookahead += 1;
+                    }
+                    if lookahead < bytes.len() && bytes[lookahead].is_ascii_digit() {
+                        index = lookahead;
+                        while index < bytes.len() && bytes[index].is_ascii_digit() {
+                            index += 1;
+                        }
+                    }
+                }
+                let text = &input[start..index];
+                let value = text.parse::<f64>().map_err(|_| {
+                    ParseError::new(start..index, format!("invalid number `{}`", text))
+                })?;
+                tokens.push((Token::Number(value), start..index));
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while index < bytes.len()
+                    && (bytes[index].is_ascii_alphanumeric() || bytes[index] == b'_')
+                {
+                    index += 1;
+                }
+                // Primes mark derivatives of custom functions, e.g. `f'(x)`.
+                while index < bytes.len() && bytes[index] == b'\'' {
+                    index += 1;
+                }
+                tokens.push((Token::Ident(input[start..index].to_string()), start..index));
+                continue;
+            }
+            _ => {
+                let width = input[start..].chars().next().map_or(1, char::len_utf8);
+                return Err(ParseError::new(
+                    start..start + width,
+                    format!("unexpected character `{}`", &input[start..start + width]),
+                ));
+            }
+        };
+        index += 1;
+        tokens.push((token, start..index));
+    }
+
+    tokens.push((Token::End, input.len()..input.len()));
+    Ok(tokens)
+}
+
+fn function(name: &str) -> Option<FunctionType> {
+    use HyperbolicFunction as H;
+    use InverseHyperbolicFunction as IH;
+    use InverseTrigonometricFunction as IT;
+    use SpecialFunction as S;
+    use TrigonometricFunction as T;
+
+    Some(match name {
+        "ln" | "log" => FunctionType::Logarithm,
+        "log2" => FunctionType::LogarithmBase(2.0),
+        "log10" => FunctionType::LogarithmBase(10.0),
+        "exp" => FunctionType::Exponential,
+        "sqrt" => FunctionType::SquareRoot,
+        "sin" => FunctionType::Trigonometric(T::Sine),
+        "cos" => FunctionType::Trigonometric(T::Cosine),
+        "tan" => FunctionType::Trigonometric(T::Tangent),
+        "cot" => FunctionType::Trigonometric(T::Cotangent),
+        "sec" => FunctionType::Trigonometric(T::Secant),
+        "cosec" | "csc" => FunctionType::Trigonometric(T::Cosecant),
+        "asin" | "arcsin" => FunctionType::InverseTrigonometric(IT::Arcsine),
+        "acos" | "arccos" => FunctionType::InverseTrigonometric(IT::Arccosine),
+        "atan" | "arctan" => FunctionType::InverseTrigonometric(IT::Arctangent),
+        "acot" | "arccot" => FunctionType::InverseTrigonometric(IT::Arccotangent),
+        "asec" | "arcsec" => FunctionType::InverseTrigonometric(IT::Arcsecant),
+        "acsc" | "acosec" | "arccsc" | "arccosec" => {
+            FunctionType::InverseTrigonometric(IT::Arccosecant)
+        }
+        "sinh" => FunctionType::Hyperbolic(H::Sine),
+        "cosh" => FunctionType::Hyperbolic(H::Cosine),
+        "tanh" => FunctionType::Hyperbolic(H::Tangent),
+        "coth" => FunctionType::Hyperbolic(H::Cotangent),
+        "sech" => FunctionType::Hyperbolic(H::Secant),
+        "csch" | "cosech" => FunctionType::Hyperbolic(H::Cosecant),
+        "asinh" | "arsinh" => FunctionType::InverseHyperbolic(IH::Arsine),
+        "acosh" | "arcosh" => FunctionType::InverseHyperbolic(IH::Arcosine),
+        "atanh" | "artanh" => FunctionType::InverseHyperbolic(IH::Artangent),
+        "acoth" | "arcoth" => FunctionType::InverseHyperbolic(IH::Arcotangent),
+        "asech" | "arsech" => FunctionType::InverseHyperbolic(IH::Arsecant),
+        "acsch" | "arcsch" => FunctionType::InverseHyperbolic(IH::Arcosecant),
+        "tgamma" | "Gamma" => FunctionType::Special(S::Gamma),
+        "lgamma" => FunctionType::Special(S::LogGamma),
+        "digamma" => FunctionType::Special(S::Polygamma(0)),
+        "erf" => FunctionType::Special(S::Erf),
+        "erfc" => FunctionType::Special(S::Erfc),
+        "besselj0" => FunctionType::Special(S::BesselJ0),
+        "besselj1" => FunctionType::Special(S::BesselJ1),
+        "bessely0" => FunctionType::Special(S::BesselY0),
+        "bessely1" => FunctionType::Special(S::BesselY1),
+        "lambertw" => FunctionType::Special(S::LambertW),
+        "normcdf" => FunctionType::Special(S::NormalCdf),
+        _ => return None,
+    })
+}
+
+/// Recursive descent parser over the token stream, one method per
+/// precedence level.
+struct Parser<'a> {
+    tokens: &'a [(Token, Range<usize>)],
+    position: usize,
+    registry: &'a Registry,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &'a Token {
+        &self.tokens[self.position].0
+    }
+
+    fn span(&self) -> Range<usize> {
+        self.tokens[self.position].1.clone()
+    }
+
+    fn advance(&mut self) -> (&'a Token, Range<usize>) {
+        let (token, span) = &self.tokens[self.position];
+        if *token != Token::End {
+            self.position += 1;
+        }
+        (token, span.clone())
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<Range<usize>, ParseError> {
+        if *self.peek() == expected {
+            Ok(self.advance().1)
+        } else {
+            Err(ParseError::new(
+                self.span(),
+                format!("expected {}, found {}", expected, self.peek()),
+            ))
+        }
+    }
+
+    /// sum := product (('+' | '-') product)*
+    fn sum(&mut self) -> Result<Expression, ParseError> {
+        let mut lhs = self.product()?;
+        loop {
+            match self.peek() {
+                Token::Plus => {
+                    self.advance();
+                    lhs = lhs + self.product()?;
+                }
+                Token::Minus => {
+                    self.advance();
+                    lhs = lhs - self.product()?;
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    /// product := unary (('*' | '/')? unary)*
+    ///
+    /// A missing operator before an identifier, `(` or `{` is an implicit
+    /// multiplication, so `2.5x^3` and `2(x + 1)` are accepted. The keywords
+    /// of the cases form end a product instead.
+    fn product(&mut self) -> Result<Expression, ParseError> {
+        let mut lhs = self.unary()?;
+        loop {
+            match self.peek() {
+                Token::Star => {
+                    self.advance();
+                    lhs = lhs * self.unary()?;
+                }
+                Token::Slash => {
+                    self.advance();
+                    lhs = lhs / self.unary()?;
+                }
+                Token::Ident(name) if name == "if" || name == "otherwise" => return Ok(lhs),
+                Token::Ident(_) | Token::LParen | Token::LBrace => {
+                    lhs = lhs * self.power()?;
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    /// unary := '-' unary | '+' unary | power
+    fn unary(&mut self) -> Result<Expression, ParseError> {
+        match self.peek() {
+            Token::Minus => {
+                self.advance();
+                Ok(-self.unary()?)
+            }
+            Token::Plus => {
+                self.advance();
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// power := primary ('^' unary)?
+    fn power(&mut self) -> Result<Expression, ParseError> {
+        let base = self.primary()?;
+        if *self.peek() != Token::Caret {
+            return Ok(base);
+        }
+        self.advance();
+        let exponent = self.unary()?;
+        if !exponent.is_constant() {
+            return Ok(Expression::power(base, exponent));
+        }
+        let exponent = exponent.evaluate(0.0);
+
+        if let Some(value) = base.as_constant() {
+            return Ok(Expression::constant(value.powf(exponent)));
+        }
+        // Non-negative integer powers of a polynomial are expanded up to
+        // `MAX_EXPANDED_DEGREE`, every other power is kept as a power node.
+        match base.as_polynomial() {
+            Some(_) if exponent == 0.0 => Ok(Expression::constant(1.0)),
+            Some(p)
+                if exponent > 0.0
+                    && exponent.fract() == 0.0
+                    && exponent * p.deg as f64 <= MAX_EXPANDED_DEGREE as f64 =>
+            {
+                let mut result = base.clone();
+                for _ in 1..exponent as u32 {
+                    result = result * &base;
+                }
+                Ok(result)
+            }
+            _ => Ok(Expression::power(base, Expression::constant(exponent))),
+        }
+    }
+
+    /// arguments := '(' sum (',' sum)* ')', with exactly `N` arguments.
+    fn arguments<const N: usize>(&mut self) -> Result<[Expression; N], ParseError> {
+        self.expect(Token::LParen)?;
+        let mut arguments = Vec::with_capacity(N);
+        for position in 0..N {
+            if position > 0 {
+                self.expect(Token::Comma)?;
+            }
+            arguments.push(self.sum()?);
+        }
+        self.expect(Token::RParen)?;
+        Ok(arguments.try_into().unwrap())
+    }
+
+    /// cases := '{' (sum 'if' condition ';')* sum 'otherwise' '}'
+    ///
+    /// The form piecewise expressions are printed in, e.g.
+    /// `{-x if x < 0; x otherwise}`.
+    fn cases(&mut self) -> Result<Expression, ParseError> {
+        let mut branches = Vec::new();
+        loop {
+            let value = self.sum()?;
+            match self.peek() {
+                Token::Ident(name) if name == "if" => {
+                    self.advance();
+                    branches.push((self.condition()?, value));
+                    self.expect(Token::Semicolon)?;
+                }
+                Token::Ident(name) if name == "otherwise" => {
+                    self.advance();
+                    self.expect(Token::RBrace)?;
+                    return Ok(Expression::piecewise(branches, value));
+                }
+                other => {
+                    return Err(ParseError::new(
+                        self.span(),
+                        format!("expected `if` or `otherwise`, found {}", other),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// condition := sum relation sum
+    fn condition(&mut self) -> Result<Condition, ParseError> {
+        let lhs = self.sum()?;
+        match self.peek() {
+            Token::Relation(relation) => {
+                self.advance();
+                Ok(Condition::new(lhs, *relation, self.sum()?))
+            }
+            other => Err(ParseError::new(
+                self.span(),
+                format!("expected a comparison, found {}", other),
+            )),
+        }
+    }
+
+    /// primary := number | 'pi' | 'e' | variable | function '(' sum ')'
+    ///          | 'log' '(' sum ',' sum ')' | builtin arguments | '(' sum ')'
+    ///          | cases
+    /// builtin := 'atan2' | 'min' | 'max' | 'abs' | 'sign' | 'heaviside' | 'clamp'
+    ///          | 'polygamma'
+    fn primary(&mut self) -> Result<Expression, ParseError> {
+        let (token, span) = self.advance();
+        match token {
+            Token::Number(value) => Ok(Expression::constant(*value)),
+            Token::LParen => {
+                let inner = self.sum()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Token::LBrace => self.cases(),
+            Token::Ident(name) => match name.as_str() {
+                "pi" => Ok(Expression::constant(std::f64::consts::PI)),
+                "e" => Ok(Expression::constant(std::f64::consts::E)),
+                "atan2" | "min" | "max" => {
+                    let [a, b] = self.arguments()?;
+                    Ok(match name.as_str() {
+                        "atan2" => Expression::atan2(a, b),
+                        "min" => Expression::min(a, b),
+                        _ => Expression::max(a, b),
+                    })
+                }
+                "abs" | "sign" | "sgn" | "heaviside" => {
+                    let [argument] = self.arguments()?;
+                    Ok(match name.as_str() {
+                        "abs" => Expression::abs(argument),
+                        "heaviside" => Expression::heaviside(argument),
+                        _ => Expression::sign(argument),
+                    })
+                }
+                "polygamma" => {
+                    let [order, argument] = self.arguments()?;
+                    match order.as_constant() {
+                        Some(n) if n >= 0.0 && n.fract() == 0.0 => {
+                            Ok(SpecialFunction::Polygamma(n as u32).of(argument))
+                        }
+                        _ => Err(ParseError::new(
+                            span,
+                            "the order of polygamma must be a non-negative integer",
+                        )),
+                    }
+                }
+                "clamp" => {
+                    let [argument, low, high] = self.arguments()?;
+                    Ok(Expression::clamp(argument, low, high))
+                }
+                _ => match function(name).or_else(|| self.registry.function(name)) {
+                    Some(function) => {
+                        if *self.peek() != Token::LParen {
+                            return Err(ParseError::new(
+                                self.span(),
+                                format!("expected `(` after function `{}`", name),
+                            ));
+                        }
+                        self.advance();
+                        let argument = self.sum()?;
+                        // log(u, b) takes the base as a second argument.
+                        let function = if name == "log" && *self.peek() == Token::Comma {
+                            self.advance();
+                            let start = self.span();
+                            match self.sum()?.as_constant() {
+                                Some(base) => FunctionType::LogarithmBase(base),
+                                None => {
+                                    return Err(ParseError::new(
+                                        start,
+                                        "the base of a logarithm must be constant",
+                                    ))
+                                }
+                            }
+                        } else {
+                            function
+                        };
+                        self.expect(Token::RParen)?;
+                        Ok(Expression::apply(function, argument))
+                    }
+                    None if *self.peek() == Token::LParen || name.ends_with('\'') => Err(
+                        ParseError::new(span, format!("unknown function `{}`", name)),
+                    ),
+                    None => Ok(Expression::variable(name)),
+                },
+            },
+            Token::End => Err(ParseError::new(span, "unexpected end of input")),
+            other => Err(ParseError::new(span, format!("unexpected {}", other))),
+        }
+    }
+}
+
+/// Parses an infix expression in `x` and other named variables, such as
+/// `sin(x) * cos(2.5x^3 + 5x^2 - 2.5) / (x + 1)` or `sin(omega t + phi)`.
+pub fn parse(input: &str) -> Result<Expression, ParseError> {
+    parse_with(input, &Registry::new())
+}
+
+/// Parses like [`parse`], additionally calling the functions of `registry`.
+pub fn parse_with(input: &str, registry: &Registry) -> Result<Expression, ParseError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        registry,
+    };
+    let expr = parser.sum()?;
+    match parser.peek() {
+        Token::End => Ok(expr),
+        other => Err(ParseError::new(
+            parser.span(),
+            format!("unexpected {}", other),
+        )),
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Display;
+
+use crate::math::{
+    expression::Expression,
+    expression_type::ExpressionType,
+    func_traits::VariableFunction,
+    function_type::FunctionType,
+    render::Renderer,
+    variable::{Env, EvaluationError},
+};
+
+/// Comparison between the two sides of a [`Condition`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relation {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Relation {
+    pub fn compare(&self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Less => lhs < rhs,
+            Self::LessEqual => lhs <= rhs,
+            Self::Greater => lhs > rhs,
+            Self::GreaterEqual => lhs >= rhs,
+            Self::Equal => lhs == rhs,
+            Self::NotEqual => lhs != rhs,
+        }
+    }
+}
+
+/// `lhs <relation> rhs`, selects a branch of
+/// [`ExpressionType::Piecewise`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub lhs: Expression,
+    pub relation: Relation,
+    pub rhs: Expression,
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&Renderer::plain(f.precision()).condition(self))
+    }
+}
+
+impl Condition {
+    pub fn new(lhs: impl Into<Expression>, relation: Relation, rhs: impl Into<Expression>) -> Self {
+        Self {
+            lhs: lhs.into(),
+            relation,
+            rhs: rhs.into(),
+        }
+    }
+
+    pub fn holds(&self, x: f64) -> bool {
+        self.relation
+            .compare(self.lhs.evaluate(x), self.rhs.evaluate(x))
+    }
+
+    pub fn holds_with(&self, env: &Env) -> Result<bool, EvaluationError> {
+        Ok(self
+            .relation
+            .compare(self.lhs.evaluate_with(env)?, self.rhs.evaluate_with(env)?))
+    }
+
+    /// `lhs = rhs`, the points where the condition may switch between
+    /// holding and not holding.
+    pub fn boundary(&self) -> Self {
+        Self::new(self.lhs.clone(), Relation::Equal, self.rhs.clone())
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.lhs.is_constant() && self.rhs.is_constant()
+    }
+
+    pub fn depends_on(&self, variable: &str) -> bool {
+        self.lhs.depends_on(variable) || self.rhs.depends_on(variable)
+    }
+
+    pub fn simplify(&self) -> Self {
+        Self::new(self.lhs.simplify(), self.relation, self.rhs.simplify())
+    }
+
+    pub fn substitute(&self, variable: &str, replacement: &Expression) -> Self {
+        Self::new(
+            self.lhs.substitute(variable, replacement),
+            self.relation,
+            self.rhs.substitute(variable, replacement),
+        )
+    }
+}
+
+impl Expression {
+    /// The value of the first branch whose condition holds, `otherwise`
+    /// when none does.
+    pub fn piecewise(branches: Vec<(Condition, Expression)>, otherwise: Expression) -> Expression {
+        Expression {
+            function: FunctionType::None,
+            input: ExpressionType::Piecewise(branches, Box::new(otherwise)),
+        }
+    }
+
+    pub fn abs(argument: Expression) -> Expression {
+        Expression::piecewise(
+            vec![(
+                Condition::new(argument.clone(), Relation::Less, 0.0),
+                -&argument,
+            )],
+            argument,
+        )
+    }
+
+    /// `-1`, `0` or `1` by the sign of `argument`.
+    pub fn sign(argument: Expression) -> Expression {
+        Expression::piecewise(
+            vec![
+                (
+                    Condition::new(argument.clone(), Relation::Less, 0.0),
+                    Expression::constant(-1.0),
+                ),
+                (
+                    Condition::new(argument, Relation::Equal, 0.0),
+                    Expression::constant(0.0),
+                ),
+            ],
+            Expression::constant(1.0),
+        )
+    }
+
+    /// Unit step, taking the value `0.5` at zero.
+    pub fn heaviside(argument: Expression) -> Expression {
+        Expression::piecewise(
+            vec![
+                (
+                    Condition::new(argument.clone(), Relation::Less, 0.0),
+                    Expression::constant(0.0),
+                ),
+                (
+                    Condition::new(argument, Relation::Equal, 0.0),
+                    Expression::constant(0.5),
+                ),
+            ],
+            Expression::constant(1.0),
+        )
+    }
+
+    pub fn min(a: Expression, b: Expression) -> Expression {
+        Expression::piecewise(
+            vec![(Condition::new(a.clone(), Relation::LessEqual, b.clone()), a)],
+            b,
+        )
+    }
+
+    pub fn max(a: Expression, b: Expression) -> Expression {
+        Expression::piecewise(
+            vec![(
+                Condition::new(a.clone(), Relation::GreaterEqual, b.clone()),
+                a,
+            )],
+            b,
+        )
+    }
+
+    /// Limits `argument` to `[low, high]`.
+    pub fn clamp(argument: Expression, low: Expression, high: Expression) -> Expression {
+        Expression::piecewise(
+            vec![
+                (
+                    Condition::new(argument.clone(), Relation::Less, low.clone()),
+                    low,
+                ),
+                (
+                    Condition::new(argument.clone(), Relation::Greater, high.clone()),
+                    high,
+                ),
+            ],
+            argument,
+        )
+    }
+}
@@ -1,14 +1,16 @@
+use conv::prelude::*;
 use core::fmt;
+use itertools::Itertools;
+use num_complex::Complex;
+use num_traits::{pow::Pow, Float, Num, NumCast, One, Zero};
+use std::default::Default;
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
 };
 use Vec;
-// use crate::complex::Complex;
-use conv::prelude::*;
-use itertools::Itertools;
-use num_traits::{pow::Pow, Zero};
-use std::default::Default;
+
+use crate::math::render::{DisplayCoefficient, Renderer};
 
 pub trait PolynomialOperationTypes {}
 
@@ -25,20 +27,60 @@ impl PolynomialOperationTypes for i64 {}
 impl PolynomialOperationTypes for i128 {}
 impl PolynomialOperationTypes for f32 {}
 impl PolynomialOperationTypes for f64 {}
-// impl PolynomialOperationTypes for Complex {}
+impl<T: PolynomialOperationTypes> PolynomialOperationTypes for Complex<T> {}
+
+/// Integer powers of a polynomial are only expanded up to this degree,
+/// higher powers are kept as power nodes.
+pub const MAX_EXPANDED_DEGREE: u32 = 64;
+
+/// Coefficient types that can be integrated. Floating point coefficients
+/// integrate in place, integer coefficients are promoted to `f64` since
+/// the antiderivative divides every coefficient by its new exponent.
+/// Complex coefficients promote their real and imaginary parts alike.
+pub trait IntegralCoefficient: PolynomialOperationTypes + Copy {
+    type Output: PolynomialOperationTypes + Num + NumCast + Copy;
+
+    fn promote(self) -> Self::Output;
+}
+
+macro_rules! define_integral_coefficient {
+    ($type: ident => $output: ident) => {
+        impl IntegralCoefficient for $type {
+            type Output = $output;
+
+            fn promote(self) -> $output {
+                self as $output
+            }
+        }
+    };
+}
+
+define_integral_coefficient!(f32 => f32);
+define_integral_coefficient!(f64 => f64);
+define_integral_coefficient!(i8 => f64);
+define_integral_coefficient!(i16 => f64);
+define_integral_coefficient!(i32 => f64);
+define_integral_coefficient!(i64 => f64);
+define_integral_coefficient!(i128 => f64);
+define_integral_coefficient!(u8 => f64);
+define_integral_coefficient!(u16 => f64);
+define_integral_coefficient!(u32 => f64);
+define_integral_coefficient!(u64 => f64);
+define_integral_coefficient!(u128 => f64);
+define_integral_coefficient!(usize => f64);
 
 #[macro_export]
 macro_rules! x {
     ($number:expr) => {{
         let mut poly = vec![0.0; $number];
         poly.push(1.0);
-        Polynomial {
+        $crate::math::polynomial::Polynomial {
             poly,
-            deg: $number
+            deg: $number as u32,
         }
     }};
     () => {
-        Polynomial {
+        $crate::math::polynomial::Polynomial {
             poly: vec![0.0, 1.0],
             deg: 1,
         }
@@ -64,7 +106,7 @@ where
         + Sub<Output = T>
         + Div<Output = T>
         + Pow<T, Output = T>
-        + ValueFrom<u32>
+        + NumCast
         + Copy,
 {
     pub fn new() -> Self {
@@ -94,68 +136,360 @@ where
         }
     }
 
-    pub fn evaluate(&self, _x: T) -> T
-    where
-        T: ValueFrom<usize>,
-    {
-        self.poly
-            .iter()
-            .enumerate()
-            .map(|(index, item)| -> T { *item * _x.pow(index.value_into().unwrap()) })
-            .reduce(|accumulator, val| accumulator + val)
-            .unwrap()
+    /// Evaluates the polynomial at `x` by Horner's scheme.
+    pub fn evaluate(&self, x: T) -> T {
+        self.evaluate_at(x)
     }
 
     pub fn derivative(&self) -> Self {
+        if self.poly.len() <= 1 {
+            return Self {
+                poly: vec![T::zero()],
+                deg: 0,
+            };
+        }
         Self {
             poly: (1_u32..self.poly.len() as u32)
-                .into_iter()
-                .map(|c| -> T { T::value_from(c).unwrap() * self.poly[c as usize] })
+                .map(|c| -> T { <T as NumCast>::from(c).unwrap() * self.poly[c as usize] })
                 .collect::<Vec<T>>(),
             deg: self.deg - 1,
         }
     }
+}
 
-    pub fn to_string(&self) -> String {
-        self.poly
-            .iter()
-            .enumerate()
-            .filter(|(_index, x)| T::value_from(0).unwrap() != **x)
-            .map(|(index, val)| -> String {
-                match index {
-                    0 => format!("{}", val),
-                    1 => format!("{}x", val),
-                    _ => format!("{}x^({})", val, index),
+impl<T: PolynomialOperationTypes + Copy + Zero> Polynomial<T> {
+    /// Drops vanishing leading coefficients so that `deg` matches the
+    /// highest non-zero term, the zero polynomial is kept as `[0]`.
+    pub fn normalized(mut self) -> Self {
+        while self.poly.len() > 1 && self.poly[self.poly.len() - 1].is_zero() {
+            self.poly.pop();
+        }
+        if self.poly.is_empty() {
+            self.poly.push(T::zero());
+        }
+        self.deg = self.poly.len() as u32 - 1;
+        self
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.poly.iter().all(|c| c.is_zero())
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + Copy + Zero + One + Mul<Output = T>,
+{
+    /// Returns the composition `p(q(x))` of this polynomial `p` with
+    /// `other`, by Horner's scheme.
+    pub fn compose(&self, other: &Polynomial<T>) -> Polynomial<T> {
+        let mut result: Vec<T> = Vec::new();
+        for c in self.poly.iter().rev() {
+            let mut next = vec![T::zero(); (result.len() + other.poly.len()).max(2) - 1];
+            for (i, a) in result.iter().enumerate() {
+                for (j, b) in other.poly.iter().enumerate() {
+                    next[i + j] = next[i + j] + *a * *b;
                 }
-            })
-            .collect::<Vec<String>>()
-            .join(" + ")
+            }
+            next[0] = next[0] + *c;
+            result = next;
+        }
+        Polynomial {
+            deg: result.len().max(1) as u32 - 1,
+            poly: result,
+        }
+        .normalized()
+    }
+
+    /// Taylor shift, returns `p(x + a)`.
+    pub fn shift(&self, a: T) -> Polynomial<T> {
+        self.compose(&Polynomial {
+            poly: vec![a, T::one()],
+            deg: 1,
+        })
+    }
+
+    /// Returns `p(k x)`.
+    pub fn scale(&self, k: T) -> Polynomial<T> {
+        let mut power = T::one();
+        Polynomial {
+            poly: self
+                .poly
+                .iter()
+                .map(|c| {
+                    let value = *c * power;
+                    power = power * k;
+                    value
+                })
+                .collect::<Vec<T>>(),
+            deg: self.deg,
+        }
+    }
+}
+
+/// Division with remainder and common divisors, for floating point
+/// coefficients. Coefficients at most `tolerance` times the largest one are
+/// treated as zero, the methods without a tolerance use
+/// [`Polynomial::DEFAULT_TOLERANCE`].
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + Float + AddAssign + Default,
+{
+    /// `sqrt(epsilon)`, loose enough for the rounding error accumulated by
+    /// a Euclidean GCD.
+    pub const DEFAULT_TOLERANCE: f64 = 1.490_116_119_384_765_6e-8;
+
+    fn default_tolerance() -> T {
+        T::from(Self::DEFAULT_TOLERANCE).unwrap()
+    }
+
+    fn largest_coefficient(&self) -> T {
+        self.poly.iter().fold(T::zero(), |m, c| m.max(c.abs()))
+    }
+
+    /// Sets coefficients of magnitude at most `threshold` to zero.
+    fn chopped(mut self, threshold: T) -> Self {
+        self.poly
+            .iter_mut()
+            .filter(|c| c.abs() <= threshold)
+            .for_each(|c| *c = T::zero());
+        self.normalized()
+    }
+
+    /// Drops coefficients that are negligible relative to the largest one.
+    pub fn trimmed(self, tolerance: T) -> Self {
+        let threshold = tolerance * self.largest_coefficient();
+        self.chopped(threshold)
+    }
+
+    /// Scales the polynomial to a leading coefficient of one, `None` for
+    /// the zero polynomial.
+    pub fn monic(&self) -> Option<Self> {
+        let value = self.clone().normalized();
+        let lead = value.poly[value.deg as usize];
+        (!lead.is_zero()).then(|| value / lead)
+    }
+
+    /// Quotient and remainder of the division by `divisor`, with the degree
+    /// of the remainder below that of the divisor. `None` when the divisor
+    /// is zero.
+    pub fn div_rem(&self, divisor: &Polynomial<T>) -> Option<(Self, Self)> {
+        self.div_rem_with_tolerance(divisor, Self::default_tolerance())
+    }
+
+    pub fn div_rem_with_tolerance(
+        &self,
+        divisor: &Polynomial<T>,
+        tolerance: T,
+    ) -> Option<(Self, Self)> {
+        let divisor = divisor.clone().trimmed(tolerance);
+        if divisor.is_zero() {
+            return None;
+        }
+        let n = divisor.deg as usize;
+        let lead = divisor.poly[n];
+        let mut remainder = self.clone().normalized();
+        let scale = remainder.largest_coefficient();
+        if remainder.deg < divisor.deg {
+            return Some((Polynomial::zero(), remainder));
+        }
+        let mut quotient = vec![T::zero(); remainder.poly.len() - n];
+        for i in (0..quotient.len()).rev() {
+            let c = remainder.poly[i + n] / lead;
+            quotient[i] = c;
+            for (j, d) in divisor.poly.iter().enumerate() {
+                remainder.poly[i + j] = remainder.poly[i + j] - c * *d;
+            }
+            remainder.poly[i + n] = T::zero();
+        }
+        Some((
+            Polynomial {
+                deg: quotient.len() as u32 - 1,
+                poly: quotient,
+            }
+            .normalized(),
+            remainder.chopped(tolerance * scale),
+        ))
+    }
+
+    /// Monic greatest common divisor by Euclid's algorithm, zero only when
+    /// both polynomials are.
+    pub fn gcd(&self, other: &Polynomial<T>) -> Self {
+        self.gcd_with_tolerance(other, Self::default_tolerance())
+    }
+
+    pub fn gcd_with_tolerance(&self, other: &Polynomial<T>, tolerance: T) -> Self {
+        self.extended_gcd_with_tolerance(other, tolerance).0
+    }
+
+    /// Returns `(g, s, t)` with `g` the monic greatest common divisor and
+    /// `s * self + t * other = g` (Bézout's identity).
+    pub fn extended_gcd(&self, other: &Polynomial<T>) -> (Self, Self, Self) {
+        self.extended_gcd_with_tolerance(other, Self::default_tolerance())
+    }
+
+    pub fn extended_gcd_with_tolerance(
+        &self,
+        other: &Polynomial<T>,
+        tolerance: T,
+    ) -> (Self, Self, Self) {
+        let one = Polynomial {
+            poly: vec![T::one()],
+            deg: 0,
+        };
+        // Invariant: s * self + t * other = r for both rows, the current
+        // row is rescaled to a monic remainder to keep the scale in check.
+        let mut previous = (self.clone().trimmed(tolerance), one.clone(), Self::zero());
+        let mut current = (other.clone().trimmed(tolerance), Self::zero(), one);
+        while !current.0.is_zero() {
+            let lead = current.0.poly[current.0.deg as usize];
+            current = (current.0 / lead, current.1 / lead, current.2 / lead);
+            let (q, remainder) = previous
+                .0
+                .div_rem_with_tolerance(&current.0, tolerance)
+                .unwrap();
+            let next = (
+                remainder,
+                (previous.1 - &(q.clone() * &current.1)).normalized(),
+                (previous.2 - &(q * &current.2)).normalized(),
+            );
+            previous = std::mem::replace(&mut current, next);
+        }
+        match previous.0.is_zero() {
+            true => previous,
+            false => {
+                let lead = previous.0.poly[previous.0.deg as usize];
+                (previous.0 / lead, previous.1 / lead, previous.2 / lead)
+            }
+        }
+    }
+
+    /// Monic least common multiple, zero when either polynomial is.
+    pub fn lcm(&self, other: &Polynomial<T>) -> Self {
+        let gcd = self.gcd(other);
+        if gcd.is_zero() || self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let (quotient, _) = self.div_rem(&gcd).unwrap();
+        (quotient * other).normalized().monic().unwrap()
+    }
+
+    fn zero() -> Self {
+        Polynomial {
+            poly: vec![T::zero()],
+            deg: 0,
+        }
+    }
+}
+
+/// Polynomial division, see [`Polynomial::div_rem`].
+///
+/// # Panics
+///
+/// When the divisor is zero, like integer division.
+impl<'b, T> Div<&'b Polynomial<T>> for &Polynomial<T>
+where
+    T: PolynomialOperationTypes + Float + AddAssign + Default,
+{
+    type Output = Polynomial<T>;
+
+    fn div(self, rhs: &'b Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(rhs)
+            .expect("division by the zero polynomial")
+            .0
+    }
+}
+
+impl<'b, T> Div<&'b Polynomial<T>> for Polynomial<T>
+where
+    T: PolynomialOperationTypes + Float + AddAssign + Default,
+{
+    type Output = Polynomial<T>;
+
+    fn div(self, rhs: &'b Polynomial<T>) -> Polynomial<T> {
+        &self / rhs
+    }
+}
+
+/// Remainder of the polynomial division, see [`Polynomial::div_rem`].
+///
+/// # Panics
+///
+/// When the divisor is zero, like integer division.
+impl<'b, T> Rem<&'b Polynomial<T>> for &Polynomial<T>
+where
+    T: PolynomialOperationTypes + Float + AddAssign + Default,
+{
+    type Output = Polynomial<T>;
+
+    fn rem(self, rhs: &'b Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(rhs)
+            .expect("division by the zero polynomial")
+            .1
+    }
+}
+
+impl<'b, T> Rem<&'b Polynomial<T>> for Polynomial<T>
+where
+    T: PolynomialOperationTypes + Float + AddAssign + Default,
+{
+    type Output = Polynomial<T>;
+
+    fn rem(self, rhs: &'b Polynomial<T>) -> Polynomial<T> {
+        &self % rhs
+    }
+}
+
+impl<T: IntegralCoefficient> Polynomial<T> {
+    /// Returns the antiderivative with integration constant `constant`.
+    pub fn integral(&self, constant: T::Output) -> Polynomial<T::Output> {
+        Polynomial {
+            poly: std::iter::once(constant)
+                .chain(self.poly.iter().enumerate().map(|(index, c)| {
+                    c.promote() / <T::Output as NumCast>::from(index + 1).unwrap()
+                }))
+                .collect::<Vec<T::Output>>(),
+            deg: self.deg + 1,
+        }
+        .normalized()
+    }
+
+    /// Returns the integral of the polynomial over `[a, b]`.
+    pub fn definite_integral(&self, a: T::Output, b: T::Output) -> T::Output {
+        let antiderivative = self.integral(T::Output::zero());
+        antiderivative.evaluate_at(b) - antiderivative.evaluate_at(a)
+    }
+}
+
+impl<T: PolynomialOperationTypes + Copy> Default for Polynomial<T> {
+    fn default() -> Self {
+        Self {
+            poly: Vec::<T>::new(),
+            deg: 0_u32,
+        }
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + DisplayCoefficient,
+{
+    /// Renders the polynomial in `variable` instead of `x`.
+    pub fn to_string_in(&self, variable: &str) -> String {
+        Renderer::plain(None).polynomial(self, variable).0
     }
 }
 
-impl<'b, T> Display for Polynomial<T>
+impl<T> Display for Polynomial<T>
 where
-    T: PolynomialOperationTypes + conv::ValueFrom<T> + conv::ValueInto<T> + fmt::Display + Copy + Zero + PartialEq,
+    T: PolynomialOperationTypes + DisplayCoefficient,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let str_output = self
-            .poly
-            .iter()
-            .enumerate()
-            .map(|(index, val)| -> String {
-                match index {
-                    0 => format!("{}", val),
-                    1 => format!("{}x", val),
-                    _ => format!("{}x^({})", val, self.poly.len() - index - 1),
-                }
-            })
-            .collect::<Vec<String>>()
-            .join(" + ");
-        f.write_str(format!("Poly({})", str_output).as_str())
+        f.write_str(&Renderer::plain(f.precision()).polynomial(self, "x").0)
     }
 }
 
-impl<'a, T> Add<Polynomial<T>> for  Polynomial<T>
+impl<T> Add<Polynomial<T>> for Polynomial<T>
 where
     T: PolynomialOperationTypes + Copy + Add<Output = T>,
 {
@@ -183,7 +517,7 @@ where
     }
 }
 
-impl<'a, 'b, T> Add<&'b Polynomial<T>> for &'a Polynomial<T>
+impl<'b, T> Add<&'b Polynomial<T>> for &Polynomial<T>
 where
     T: PolynomialOperationTypes + Copy + Add<Output = T>,
 {
@@ -211,7 +545,7 @@ where
     }
 }
 
-impl<'a, T: Copy + Add<Output = T> + AddAssign> Add<T> for Polynomial<T>
+impl<T: Copy + Add<Output = T> + AddAssign> Add<T> for Polynomial<T>
 where
     T: PolynomialOperationTypes,
 {
@@ -231,19 +565,17 @@ where
     T: PolynomialOperationTypes,
 {
     fn add_assign(&mut self, rhs: &'b Polynomial<T>) {
-        if rhs.poly.len() > self.poly.len() {
-            self.poly.extend(rhs.poly.iter().skip(self.poly.len()));
-        }
-        if rhs.poly.len() < self.poly.len() {
-            self.poly[..rhs.poly.len()]
-                .iter_mut()
-                .enumerate()
-                .for_each(|(index, elem)| *elem += rhs.poly[index]);
-        }
+        let overlap = self.poly.len().min(rhs.poly.len());
+        self.poly[..overlap]
+            .iter_mut()
+            .zip(&rhs.poly[..overlap])
+            .for_each(|(elem, c)| *elem += *c);
+        self.poly.extend(rhs.poly.iter().skip(overlap));
+        self.deg = self.deg.max(rhs.deg);
     }
 }
 
-impl<'a, T: Copy + AddAssign> AddAssign<T> for Polynomial<T>
+impl<T: Copy + AddAssign> AddAssign<T> for Polynomial<T>
 where
     T: PolynomialOperationTypes,
 {
@@ -278,7 +610,7 @@ where
     }
 }
 
-impl<'b, T: Copy + Mul<Output = T> + AddAssign + Default + PartialEq> Mul<T> for Polynomial<T>
+impl<T: Copy + Mul<Output = T> + AddAssign + Default + PartialEq> Mul<T> for Polynomial<T>
 where
     T: PolynomialOperationTypes,
 {
@@ -312,7 +644,7 @@ where
 
         *self = Polynomial {
             poly: new_poly,
-            deg: (self.poly.len() + rhs.poly.len() - 1) as u32,
+            deg: (self.poly.len() + rhs.poly.len() - 2) as u32,
         }
     }
 }
@@ -322,20 +654,14 @@ where
     T: PolynomialOperationTypes,
 {
     fn sub_assign(&mut self, rhs: &'b Polynomial<T>) {
-        if rhs.poly.len() > self.poly.len() {
-            self.poly.extend(
-                rhs.poly
-                    .iter()
-                    .skip(self.poly.len())
-                    .map(|item| -> T { -*item }),
-            );
-        }
-        if rhs.poly.len() < self.poly.len() {
-            self.poly[..rhs.poly.len()]
-                .iter_mut()
-                .enumerate()
-                .for_each(|(index, elem)| *elem -= rhs.poly[index]);
-        }
+        let overlap = self.poly.len().min(rhs.poly.len());
+        self.poly[..overlap]
+            .iter_mut()
+            .zip(&rhs.poly[..overlap])
+            .for_each(|(elem, c)| *elem -= *c);
+        self.poly
+            .extend(rhs.poly.iter().skip(overlap).map(|item| -> T { -*item }));
+        self.deg = self.deg.max(rhs.deg);
     }
 }
 
@@ -401,7 +727,7 @@ where
             poly: self.poly.clone(),
             deg: self.deg,
         };
-        p.poly[0] -= rhs.value_into().unwrap();
+        p.poly[0] -= rhs;
         p
     }
 }
@@ -497,8 +823,8 @@ macro_rules! define_commutative_operators {
 
         ///////////////////////////////////////////////////////////////////////////////////////////////
 
-        impl<'b, T: Copy + Mul<Output = T> + SubAssign + Default + PartialEq + Sized>
-            Sub<&'b Polynomial<T>> for $type
+        impl<'b, T: Copy + Sub<Output = T> + Default + PartialEq + Sized> Sub<&'b Polynomial<T>>
+            for $type
         where
             T: PolynomialOperationTypes + ValueFrom<<T>::Output> + conv::ValueFrom<$type>,
         {
@@ -506,28 +832,26 @@ macro_rules! define_commutative_operators {
 
             fn sub(self, rhs: &'b Polynomial<T>) -> Self::Output {
                 let mut value = Polynomial {
-                    poly: rhs.poly.iter().map(|x| *x).collect::<Vec<T>>(),
+                    poly: rhs
+                        .poly
+                        .iter()
+                        .map(|x| T::default() - *x)
+                        .collect::<Vec<T>>(),
                     deg: rhs.deg,
                 };
-                value.poly[0] -= self.value_into().unwrap();
+                value.poly[0] = T::value_from(self).unwrap() - rhs.poly[0];
                 value
             }
         }
 
-        impl<T: Copy + Mul<Output = T> + SubAssign + Default + PartialEq + Sized> Sub<Polynomial<T>>
-            for $type
+        impl<T: Copy + Sub<Output = T> + Default + PartialEq + Sized> Sub<Polynomial<T>> for $type
         where
             T: PolynomialOperationTypes + ValueFrom<<T>::Output> + conv::ValueFrom<$type>,
         {
             type Output = Polynomial<T>;
 
             fn sub(self, rhs: Polynomial<T>) -> Self::Output {
-                let mut value = Polynomial {
-                    poly: rhs.poly.into_iter().map(|x| x).collect::<Vec<T>>(),
-                    deg: rhs.deg,
-                };
-                value.poly[0] -= self.value_into().unwrap();
-                value
+                self - &rhs
             }
         }
     };
@@ -0,0 +1,684 @@
+use std::fmt::Display;
+
+use num_traits::{One, Zero};
+
+use crate::math::{
+    custom::Custom,
+    expression::Expression,
+    expression_type::ExpressionType,
+    function_type::FunctionType,
+    hyperbolic::{HyperbolicFunction, InverseHyperbolicFunction},
+    inverse_trigonometric::InverseTrigonometricFunction,
+    piecewise::{Condition, Relation},
+    polynomial::{Polynomial, PolynomialOperationTypes},
+    special::SpecialFunction,
+    trigonometric::TrigonometricFunction,
+};
+
+/// Output dialect of the [`Renderer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Notation {
+    Plain,
+    Latex,
+}
+
+/// Binding strength of a rendered fragment, used to decide where brackets
+/// are actually needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Sum,
+    /// A product-level fragment with a leading minus sign, such as `-3`.
+    Negation,
+    Product,
+    Power,
+    Atom,
+}
+
+/// Polynomial coefficients the [`Renderer`] can write, as a sign in front
+/// of a magnitude.
+pub trait DisplayCoefficient: Copy + PartialEq + Zero + One {
+    /// Splits off a leading minus sign, returns whether there was one and
+    /// the remaining magnitude.
+    fn split_sign(self) -> (bool, Self);
+
+    /// Renders a magnitude from [`DisplayCoefficient::split_sign`] with how
+    /// tightly it binds. In front of a power of the variable a product is
+    /// set apart by a space and a sum is bracketed.
+    fn render(self, renderer: &Renderer) -> (String, Precedence);
+}
+
+macro_rules! define_display_coefficient {
+    ($type: ident) => {
+        impl DisplayCoefficient for $type {
+            fn split_sign(self) -> (bool, Self) {
+                match self < Self::zero() {
+                    true => (true, Self::zero() - self),
+                    false => (false, self),
+                }
+            }
+
+            fn render(self, renderer: &Renderer) -> (String, Precedence) {
+                (renderer.number(self), Precedence::Atom)
+            }
+        }
+    };
+}
+
+define_display_coefficient!(f64);
+define_display_coefficient!(f32);
+define_display_coefficient!(i128);
+define_display_coefficient!(i64);
+define_display_coefficient!(i32);
+define_display_coefficient!(i16);
+define_display_coefficient!(i8);
+define_display_coefficient!(u128);
+define_display_coefficient!(u64);
+define_display_coefficient!(u32);
+define_display_coefficient!(u16);
+define_display_coefficient!(u8);
+define_display_coefficient!(usize);
+
+/// Precedence-aware printer shared by the `Display` and `ToLatex`
+/// implementations, every method returns the rendered fragment together
+/// with how tightly it binds.
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    pub notation: Notation,
+    pub precision: Option<usize>,
+}
+
+impl Renderer {
+    pub fn plain(precision: Option<usize>) -> Self {
+        Self {
+            notation: Notation::Plain,
+            precision,
+        }
+    }
+
+    pub fn latex() -> Self {
+        Self {
+            notation: Notation::Latex,
+            precision: None,
+        }
+    }
+    pub fn number<T: Display>(&self, value: T) -> String {
+        match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        }
+    }
+
+    pub fn parenthesize(&self, inner: &str) -> String {
+        match self.notation {
+            Notation::Plain => format!("({})", inner),
+            Notation::Latex => format!("\\left({}\\right)", inner),
+        }
+    }
+
+    pub fn bracket(
+        &self,
+        (output, precedence): (String, Precedence),
+        required: Precedence,
+    ) -> String {
+        if precedence < required {
+            self.parenthesize(&output)
+        } else {
+            output
+        }
+    }
+
+    /// Raises an already bracketed `base` to `exponent`.
+    pub fn power(&self, base: &str, exponent: &str) -> String {
+        match self.notation {
+            Notation::Plain => format!("{}^{}", base, exponent),
+            Notation::Latex => format!("{}^{{{}}}", base, exponent),
+        }
+    }
+
+    /// Joins two factors of a product.
+    fn times(&self, output: &mut String, factor: &str) {
+        if !output.is_empty() {
+            output.push_str(match self.notation {
+                Notation::Plain => " * ",
+                Notation::Latex => match factor.chars().next() {
+                    Some(c) if c.is_ascii_digit() => " \\cdot ",
+                    _ => " ",
+                },
+            });
+        }
+        output.push_str(factor);
+    }
+
+    /// Joins `terms` into a sum, turning `+ -a` into `- a`.
+    fn sum(&self, terms: Vec<(String, Precedence)>) -> (String, Precedence) {
+        let mut output = String::new();
+        for (position, (term, _)) in terms.iter().enumerate() {
+            match (position, term.strip_prefix('-')) {
+                (0, _) => output.push_str(term),
+                (_, Some(negated)) => {
+                    output.push_str(" - ");
+                    output.push_str(negated);
+                }
+                (_, None) => {
+                    output.push_str(" + ");
+                    output.push_str(term);
+                }
+            }
+        }
+        match terms.as_slice() {
+            [] => ("0".to_string(), Precedence::Atom),
+            [(_, precedence)] => (output, *precedence),
+            _ => (output, Precedence::Sum),
+        }
+    }
+
+    /// Renders `value` in descending powers of `variable`, which must
+    /// already be bracketed as needed.
+    pub fn polynomial<T>(&self, value: &Polynomial<T>, variable: &str) -> (String, Precedence)
+    where
+        T: PolynomialOperationTypes + DisplayCoefficient,
+    {
+        let terms = value
+            .poly
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_index, c)| !c.is_zero())
+            .map(|(index, c)| {
+                let (negative, magnitude) = c.split_sign();
+                let (coefficient, precedence) = match magnitude.is_one() && index > 0 {
+                    true => (String::new(), Precedence::Atom),
+                    false => magnitude.render(self),
+                };
+                // `2i x` rather than `2ix`, and `(1 + 2i)x`.
+                let coefficient = match precedence {
+                    _ if index == 0 => coefficient,
+                    Precedence::Atom => coefficient,
+                    Precedence::Product | Precedence::Power => format!("{} ", coefficient),
+                    Precedence::Sum | Precedence::Negation => self.parenthesize(&coefficient),
+                };
+                let (term, precedence) = match index {
+                    0 => (coefficient, precedence),
+                    1 => (format!("{}{}", coefficient, variable), Precedence::Atom),
+                    _ => (
+                        format!(
+                            "{}{}",
+                            coefficient,
+                            self.power(variable, &index.to_string())
+                        ),
+                        Precedence::Power,
+                    ),
+                };
+                match (negative, index > 0 && !magnitude.is_one()) {
+                    (true, _) => (format!("-{}", term), Precedence::Negation),
+                    (false, true) => (term, Precedence::Product),
+                    (false, false) => (term, precedence),
+                }
+            })
+            .collect::<Vec<(String, Precedence)>>();
+        self.sum(terms)
+    }
+
+    /// Greek letters are written as LaTeX commands, other names with more
+    /// than one letter upright.
+    pub fn variable(&self, name: &str) -> String {
+        const GREEK: [&str; 24] = [
+            "alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa",
+            "lambda", "mu", "nu", "xi", "omicron", "pi", "rho", "sigma", "tau", "upsilon", "phi",
+            "chi", "psi", "omega",
+        ];
+        match self.notation {
+            Notation::Latex if GREEK.contains(&name.to_lowercase().as_str()) => {
+                format!("\\{}", name)
+            }
+            Notation::Latex if name.chars().count() > 1 => format!("\\mathrm{{{}}}", name),
+            _ => name.to_string(),
+        }
+    }
+
+    pub fn constant(&self, value: f64) -> (String, Precedence) {
+        if value < 0.0 {
+            (self.number(value), Precedence::Negation)
+        } else {
+            (self.number(value), Precedence::Atom)
+        }
+    }
+
+    fn trigonometric_base(&self, function: &TrigonometricFunction) -> &'static str {
+        match (self.notation, function) {
+            (Notation::Plain, TrigonometricFunction::Sine) => "sin",
+            (Notation::Plain, TrigonometricFunction::Cosine) => "cos",
+            (Notation::Plain, TrigonometricFunction::Tangent) => "tan",
+            (Notation::Plain, TrigonometricFunction::Cotangent) => "cot",
+            (Notation::Plain, TrigonometricFunction::Secant) => "sec",
+            (Notation::Plain, TrigonometricFunction::Cosecant) => "cosec",
+            (Notation::Latex, TrigonometricFunction::Sine) => "\\sin",
+            (Notation::Latex, TrigonometricFunction::Cosine) => "\\cos",
+            (Notation::Latex, TrigonometricFunction::Tangent) => "\\tan",
+            (Notation::Latex, TrigonometricFunction::Cotangent) => "\\cot",
+            (Notation::Latex, TrigonometricFunction::Secant) => "\\sec",
+            (Notation::Latex, TrigonometricFunction::Cosecant) => "\\csc",
+            (_, TrigonometricFunction::Product(..) | TrigonometricFunction::Sum(_)) => "",
+        }
+    }
+
+    /// Renders `function` applied to `argument`, products of the same
+    /// function are printed as powers, e.g. `sec(x)^2`.
+    pub fn trigonometric(
+        &self,
+        function: &TrigonometricFunction,
+        argument: Option<&str>,
+    ) -> (String, Precedence) {
+        let terms = function
+            .terms()
+            .iter()
+            .map(|(coefficient, factors)| self.trigonometric_term(*coefficient, factors, argument))
+            .collect();
+        self.sum(terms)
+    }
+
+    fn trigonometric_term(
+        &self,
+        coefficient: f64,
+        factors: &[(TrigonometricFunction, u32)],
+        argument: Option<&str>,
+    ) -> (String, Precedence) {
+        let mut output = String::new();
+        if coefficient.abs() != 1.0 || factors.is_empty() {
+            output.push_str(&self.number(coefficient.abs()));
+        }
+        for (base, count) in factors.iter() {
+            let name = self.trigonometric_base(base);
+            let factor = match (self.notation, argument, count) {
+                (_, None, 1) => name.to_string(),
+                (_, None, _) => self.power(name, &count.to_string()),
+                (Notation::Plain, Some(argument), 1) => format!("{}({})", name, argument),
+                (Notation::Plain, Some(argument), _) => {
+                    self.power(&format!("{}({})", name, argument), &count.to_string())
+                }
+                (Notation::Latex, Some(argument), 1) => {
+                    format!("{}{}", name, self.parenthesize(argument))
+                }
+                (Notation::Latex, Some(argument), _) => format!(
+                    "{}{}",
+                    self.power(name, &count.to_string()),
+                    self.parenthesize(argument)
+                ),
+            };
+            self.times(&mut output, &factor);
+        }
+
+        let precedence = match (coefficient.abs() == 1.0, factors, argument) {
+            (true, [(_, 1)], _) | (true, [_], None) => Precedence::Atom,
+            (true, [_], Some(_)) => Precedence::Power,
+            _ => Precedence::Product,
+        };
+        if coefficient < 0.0 {
+            (format!("-{}", output), Precedence::Negation)
+        } else {
+            (output, precedence)
+        }
+    }
+
+    /// A bare function, rendered in its formal variable `x`.
+    pub fn function(&self, function: &FunctionType) -> String {
+        match function {
+            FunctionType::None => String::new(),
+            FunctionType::Trigonometric(ref value) => self.trigonometric(value, None).0,
+            FunctionType::Polynomial(ref value) => self.polynomial(value, "x").0,
+            FunctionType::Logarithm => self.logarithm().to_string(),
+            FunctionType::LogarithmBase(base) => match self.notation {
+                Notation::Plain => format!("log_{}", self.number(base)),
+                Notation::Latex => format!("\\log_{{{}}}", self.number(base)),
+            },
+            FunctionType::Exponential => self.exponential().to_string(),
+            FunctionType::SquareRoot => match self.notation {
+                Notation::Plain => "sqrt".to_string(),
+                Notation::Latex => "\\sqrt".to_string(),
+            },
+            FunctionType::Power(r) => self.power("x", &self.exponent(*r)),
+            FunctionType::InverseTrigonometric(ref value) => {
+                self.inverse_trigonometric(value).to_string()
+            }
+            FunctionType::Hyperbolic(ref value) => self.hyperbolic(value).to_string(),
+            FunctionType::InverseHyperbolic(ref value) => {
+                self.inverse_hyperbolic(value).to_string()
+            }
+            FunctionType::Special(ref value) => self.special(value),
+            FunctionType::Custom(ref value) => self.custom(value),
+            FunctionType::Lambda(ref value) => self.expression(value).0,
+        }
+    }
+
+    pub fn input(&self, input: &ExpressionType) -> (String, Precedence) {
+        match input {
+            ExpressionType::Constant(value) => self.constant(*value),
+            ExpressionType::Variable(ref name) => (self.variable(name), Precedence::Atom),
+            ExpressionType::Polynomial(ref value) => self.polynomial(value, "x"),
+            ExpressionType::Expressions(ref value) => {
+                self.sum(value.iter().map(|c| self.expression(c)).collect())
+            }
+            ExpressionType::MultipliedExpressions(ref value) => {
+                // A leading factor of -1 is rendered as a plain minus sign.
+                let (negative, factors) = match value.split_first() {
+                    Some((first, rest))
+                        if first.as_constant() == Some(-1.0) && !rest.is_empty() =>
+                    {
+                        (true, rest)
+                    }
+                    _ => (false, value.as_slice()),
+                };
+                let mut output = String::new();
+                let mut precedence = Precedence::Product;
+                for (position, factor) in factors.iter().enumerate() {
+                    let factor = match (position, self.expression(factor)) {
+                        // Only the leading factor may keep its own minus sign.
+                        (0, (factor, Precedence::Negation)) if !negative => {
+                            precedence = Precedence::Negation;
+                            factor
+                        }
+                        (_, factor) => self.bracket(factor, Precedence::Product),
+                    };
+                    self.times(&mut output, &factor);
+                }
+                if negative {
+                    (format!("-{}", output), Precedence::Negation)
+                } else if factors.len() == 1 {
+                    (output, self.expression(&factors[0]).1)
+                } else {
+                    (output, precedence)
+                }
+            }
+            ExpressionType::DividedExpressions(ref num, ref den) => match self.notation {
+                Notation::Plain => {
+                    let num = self.expression(num);
+                    let precedence = num.1.min(Precedence::Product).max(Precedence::Negation);
+                    (
+                        format!(
+                            "{} / {}",
+                            self.bracket(num, Precedence::Negation),
+                            self.bracket(self.expression(den), Precedence::Power)
+                        ),
+                        precedence,
+                    )
+                }
+                Notation::Latex => (
+                    format!(
+                        "\\frac{{{}}}{{{}}}",
+                        self.expression(num).0,
+                        self.expression(den).0
+                    ),
+                    Precedence::Atom,
+                ),
+            },
+            ExpressionType::Atan2(ref y, ref x) => self.call(
+                match self.notation {
+                    Notation::Plain => "atan2",
+                    Notation::Latex => "\\operatorname{atan2}",
+                },
+                &format!("{}, {}", self.expression(y).0, self.expression(x).0),
+            ),
+            ExpressionType::Piecewise(ref branches, ref otherwise) => {
+                let cases = branches.iter().map(|(condition, value)| {
+                    (self.expression(value).0, self.condition(condition))
+                });
+                match self.notation {
+                    Notation::Plain => (
+                        format!(
+                            "{{{}}}",
+                            cases
+                                .map(|(value, condition)| format!("{} if {}; ", value, condition))
+                                .collect::<String>()
+                                + &format!("{} otherwise", self.expression(otherwise).0)
+                        ),
+                        Precedence::Atom,
+                    ),
+                    Notation::Latex => (
+                        format!(
+                            "\\begin{{cases}} {}{} & \\text{{otherwise}} \\end{{cases}}",
+                            cases
+                                .map(|(value, condition)| format!(
+                                    "{} & {} \\\\ ",
+                                    value, condition
+                                ))
+                                .collect::<String>(),
+                            self.expression(otherwise).0
+                        ),
+                        Precedence::Atom,
+                    ),
+                }
+            }
+            ExpressionType::Power(ref base, ref exponent) => {
+                let exponent = match self.notation {
+                    Notation::Plain => self.bracket(self.expression(exponent), Precedence::Atom),
+                    Notation::Latex => self.expression(exponent).0,
+                };
+                (
+                    self.power(
+                        &self.bracket(self.expression(base), Precedence::Atom),
+                        &exponent,
+                    ),
+                    Precedence::Power,
+                )
+            }
+        }
+    }
+
+    pub fn condition(&self, condition: &Condition) -> String {
+        let relation = match (self.notation, condition.relation) {
+            (_, Relation::Less) => "<",
+            (_, Relation::Greater) => ">",
+            (_, Relation::Equal) => "=",
+            (Notation::Plain, Relation::LessEqual) => "<=",
+            (Notation::Plain, Relation::GreaterEqual) => ">=",
+            (Notation::Plain, Relation::NotEqual) => "!=",
+            (Notation::Latex, Relation::LessEqual) => "\\le",
+            (Notation::Latex, Relation::GreaterEqual) => "\\ge",
+            (Notation::Latex, Relation::NotEqual) => "\\ne",
+        };
+        format!(
+            "{} {} {}",
+            self.expression(&condition.lhs).0,
+            relation,
+            self.expression(&condition.rhs).0
+        )
+    }
+
+    fn exponential(&self) -> &'static str {
+        match self.notation {
+            Notation::Plain => "exp",
+            Notation::Latex => "\\exp",
+        }
+    }
+
+    /// Renders a constant exponent, bracketed in plain notation when
+    /// negative.
+    fn exponent(&self, value: f64) -> String {
+        match self.notation {
+            Notation::Plain => self.bracket(self.constant(value), Precedence::Atom),
+            Notation::Latex => self.constant(value).0,
+        }
+    }
+
+    fn logarithm(&self) -> &'static str {
+        match self.notation {
+            Notation::Plain => "ln",
+            Notation::Latex => "\\ln",
+        }
+    }
+
+    pub fn inverse_trigonometric(&self, function: &InverseTrigonometricFunction) -> &'static str {
+        match (self.notation, function) {
+            (Notation::Plain, InverseTrigonometricFunction::Arcsine) => "asin",
+            (Notation::Plain, InverseTrigonometricFunction::Arccosine) => "acos",
+            (Notation::Plain, InverseTrigonometricFunction::Arctangent) => "atan",
+            (Notation::Plain, InverseTrigonometricFunction::Arccotangent) => "acot",
+            (Notation::Plain, InverseTrigonometricFunction::Arcsecant) => "asec",
+            (Notation::Plain, InverseTrigonometricFunction::Arccosecant) => "acsc",
+            (Notation::Latex, InverseTrigonometricFunction::Arcsine) => "\\arcsin",
+            (Notation::Latex, InverseTrigonometricFunction::Arccosine) => "\\arccos",
+            (Notation::Latex, InverseTrigonometricFunction::Arctangent) => "\\arctan",
+            (Notation::Latex, InverseTrigonometricFunction::Arccotangent) => {
+                "\\operatorname{arccot}"
+            }
+            (Notation::Latex, InverseTrigonometricFunction::Arcsecant) => "\\operatorname{arcsec}",
+            (Notation::Latex, InverseTrigonometricFunction::Arccosecant) => {
+                "\\operatorname{arccsc}"
+            }
+        }
+    }
+
+    pub fn hyperbolic(&self, function: &HyperbolicFunction) -> &'static str {
+        match (self.notation, function) {
+            (Notation::Plain, HyperbolicFunction::Sine) => "sinh",
+            (Notation::Plain, HyperbolicFunction::Cosine) => "cosh",
+            (Notation::Plain, HyperbolicFunction::Tangent) => "tanh",
+            (Notation::Plain, HyperbolicFunction::Cotangent) => "coth",
+            (Notation::Plain, HyperbolicFunction::Secant) => "sech",
+            (Notation::Plain, HyperbolicFunction::Cosecant) => "csch",
+            (Notation::Latex, HyperbolicFunction::Sine) => "\\sinh",
+            (Notation::Latex, HyperbolicFunction::Cosine) => "\\cosh",
+            (Notation::Latex, HyperbolicFunction::Tangent) => "\\tanh",
+            (Notation::Latex, HyperbolicFunction::Cotangent) => "\\coth",
+            (Notation::Latex, HyperbolicFunction::Secant) => "\\operatorname{sech}",
+            (Notation::Latex, HyperbolicFunction::Cosecant) => "\\operatorname{csch}",
+        }
+    }
+
+    pub fn inverse_hyperbolic(&self, function: &InverseHyperbolicFunction) -> &'static str {
+        match (self.notation, function) {
+            (Notation::Plain, InverseHyperbolicFunction::Arsine) => "asinh",
+            (Notation::Plain, InverseHyperbolicFunction::Arcosine) => "acosh",
+            (Notation::Plain, InverseHyperbolicFunction::Artangent) => "atanh",
+            (Notation::Plain, InverseHyperbolicFunction::Arcotangent) => "acoth",
+            (Notation::Plain, InverseHyperbolicFunction::Arsecant) => "asech",
+            (Notation::Plain, InverseHyperbolicFunction::Arcosecant) => "acsch",
+            (Notation::Latex, InverseHyperbolicFunction::Arsine) => "\\operatorname{arsinh}",
+            (Notation::Latex, InverseHyperbolicFunction::Arcosine) => "\\operatorname{arcosh}",
+            (Notation::Latex, InverseHyperbolicFunction::Artangent) => "\\operatorname{artanh}",
+            (Notation::Latex, InverseHyperbolicFunction::Arcotangent) => "\\operatorname{arcoth}",
+            (Notation::Latex, InverseHyperbolicFunction::Arsecant) => "\\operatorname{arsech}",
+            (Notation::Latex, InverseHyperbolicFunction::Arcosecant) => "\\operatorname{arcsch}",
+        }
+    }
+
+    pub fn special(&self, function: &SpecialFunction) -> String {
+        match (self.notation, function) {
+            (Notation::Plain, SpecialFunction::Gamma) => "tgamma",
+            (Notation::Plain, SpecialFunction::LogGamma) => "lgamma",
+            (Notation::Plain, SpecialFunction::Polygamma(0)) => "digamma",
+            (Notation::Plain, SpecialFunction::Polygamma(n)) => {
+                return format!("polygamma_{}", n);
+            }
+            (Notation::Plain, SpecialFunction::Erf) => "erf",
+            (Notation::Plain, SpecialFunction::Erfc) => "erfc",
+            (Notation::Plain, SpecialFunction::BesselJ0) => "besselj0",
+            (Notation::Plain, SpecialFunction::BesselJ1) => "besselj1",
+            (Notation::Plain, SpecialFunction::BesselY0) => "bessely0",
+            (Notation::Plain, SpecialFunction::BesselY1) => "bessely1",
+            (Notation::Plain, SpecialFunction::LambertW) => "lambertw",
+            (Notation::Plain, SpecialFunction::NormalCdf) => "normcdf",
+            (Notation::Latex, SpecialFunction::Gamma) => "\\Gamma",
+            (Notation::Latex, SpecialFunction::LogGamma) => "\\ln\\Gamma",
+            (Notation::Latex, SpecialFunction::Polygamma(0)) => "\\psi",
+            (Notation::Latex, SpecialFunction::Polygamma(n)) => {
+                return format!("\\psi^{{({})}}", n);
+            }
+            (Notation::Latex, SpecialFunction::Erf) => "\\operatorname{erf}",
+            (Notation::Latex, SpecialFunction::Erfc) => "\\operatorname{erfc}",
+            (Notation::Latex, SpecialFunction::BesselJ0) => "J_0",
+            (Notation::Latex, SpecialFunction::BesselJ1) => "J_1",
+            (Notation::Latex, SpecialFunction::BesselY0) => "Y_0",
+            (Notation::Latex, SpecialFunction::BesselY1) => "Y_1",
+            (Notation::Latex, SpecialFunction::LambertW) => "W_0",
+            (Notation::Latex, SpecialFunction::NormalCdf) => "\\Phi",
+        }
+        .to_string()
+    }
+
+    pub fn custom(&self, function: &Custom) -> String {
+        match self.notation {
+            Notation::Plain => function.name().to_string(),
+            Notation::Latex => format!("\\operatorname{{{}}}", function.name()),
+        }
+    }
+
+    /// Renders `name(argument)` for functions written out by name.
+    fn call(&self, name: &str, argument: &str) -> (String, Precedence) {
+        (
+            format!("{}{}", name, self.parenthesize(argument)),
+            Precedence::Atom,
+        )
+    }
+
+    pub fn expression(&self, expr: &Expression) -> (String, Precedence) {
+        match expr.function {
+            FunctionType::None => self.input(&expr.input),
+            FunctionType::Trigonometric(ref value) => {
+                self.trigonometric(value, Some(&self.input(&expr.input).0))
+            }
+            FunctionType::Polynomial(ref value) => self.polynomial(
+                value,
+                &self.bracket(self.input(&expr.input), Precedence::Atom),
+            ),
+            FunctionType::Logarithm => self.call(self.logarithm(), &self.input(&expr.input).0),
+            FunctionType::LogarithmBase(base) => match self.notation {
+                Notation::Plain => self.call(
+                    "log",
+                    &format!("{}, {}", self.input(&expr.input).0, self.number(base)),
+                ),
+                Notation::Latex => self.call(
+                    &format!("\\log_{{{}}}", self.number(base)),
+                    &self.input(&expr.input).0,
+                ),
+            },
+            FunctionType::Exponential => self.call(self.exponential(), &self.input(&expr.input).0),
+            FunctionType::SquareRoot => match self.notation {
+                Notation::Plain => self.call("sqrt", &self.input(&expr.input).0),
+                Notation::Latex => (
+                    format!("\\sqrt{{{}}}", self.input(&expr.input).0),
+                    Precedence::Atom,
+                ),
+            },
+            FunctionType::Power(r) => (
+                self.power(
+                    &self.bracket(self.input(&expr.input), Precedence::Atom),
+                    &self.exponent(r),
+                ),
+                Precedence::Power,
+            ),
+            FunctionType::InverseTrigonometric(ref value) => self.call(
+                self.inverse_trigonometric(value),
+                &self.input(&expr.input).0,
+            ),
+            FunctionType::Hyperbolic(ref value) => {
+                self.call(self.hyperbolic(value), &self.input(&expr.input).0)
+            }
+            FunctionType::InverseHyperbolic(ref value) => {
+                self.call(self.inverse_hyperbolic(value), &self.input(&expr.input).0)
+            }
+            // Plain polygamma takes the order as a first argument.
+            FunctionType::Special(SpecialFunction::Polygamma(n))
+                if n > 0 && self.notation == Notation::Plain =>
+            {
+                self.call(
+                    "polygamma",
+                    &format!("{}, {}", n, self.input(&expr.input).0),
+                )
+            }
+            FunctionType::Special(ref value) => {
+                self.call(&self.special(value), &self.input(&expr.input).0)
+            }
+            FunctionType::Custom(ref value) => {
+                self.call(&self.custom(value), &self.input(&expr.input).0)
+            }
+            FunctionType::Lambda(ref value) => self.expression(&value.substitute(
+                "x",
+                &Expression {
+                    function: FunctionType::None,
+                    input: expr.input.clone(),
+                },
+            )),
+        }
+    }
+}
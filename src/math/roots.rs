@@ -0,0 +1,404 @@
+use std::fmt::Display;
+
+use num_complex::Complex64;
+use num_traits::{Float, Zero};
+
+use crate::math::polynomial::{Polynomial, PolynomialOperationTypes};
+
+/// A zero of a polynomial, `multiplicity` counts how often it is repeated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Root<V = Complex64> {
+    pub value: V,
+    pub multiplicity: u32,
+}
+
+/// Settings of [`Polynomial::roots_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootOptions {
+    /// An approximation `z` is accepted once `|p(z)|` is at most
+    /// `tolerance` times `sum |a_k| |z|^k`, the size of the rounding error
+    /// made evaluating `p(z)`.
+    pub tolerance: f64,
+    /// Budget of Aberth–Ehrlich sweeps over all approximations.
+    pub max_iterations: usize,
+    /// Newton steps spent polishing each root.
+    pub polish_iterations: usize,
+}
+
+impl Default for RootOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: f64::EPSILON,
+            max_iterations: 500,
+            polish_iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RootError {
+    /// Every number is a root of the zero polynomial.
+    ZeroPolynomial,
+    /// A coefficient is NaN or infinite.
+    NonFinite,
+    /// The iteration budget ran out, the best approximations are still
+    /// reported.
+    NotConverged(Vec<Root>),
+}
+
+impl Display for RootError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroPolynomial => f.write_str("the zero polynomial has no isolated roots"),
+            Self::NonFinite => f.write_str("polynomial coefficients must be finite"),
+            Self::NotConverged(roots) => write!(
+                f,
+                "root finding did not converge, {} approximations reported",
+                roots.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RootError {}
+
+/// Value and derivative of the polynomial at `z`, together with
+/// `sum |a_k| |z|^k` which bounds the rounding error of the value.
+fn horner(coefficients: &[f64], z: Complex64) -> (Complex64, Complex64, f64) {
+    let modulus = z.norm();
+    coefficients.iter().rev().fold(
+        (Complex64::zero(), Complex64::zero(), 0.0),
+        |(value, derivative, scale), c| {
+            (
+                value * z + c,
+                derivative * z + value,
+                scale * modulus + c.abs(),
+            )
+        },
+    )
+}
+
+fn derivative(coefficients: &[f64]) -> Vec<f64> {
+    coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(k, c)| k as f64 * c)
+        .collect()
+}
+
+/// Simultaneous approximation of all roots by the Aberth–Ehrlich method,
+/// returns the approximations and whether all of them were accepted.
+fn aberth(coefficients: &[f64], options: &RootOptions) -> (Vec<Complex64>, bool) {
+    let n = coefficients.len() - 1;
+    // Start on a circle whose radius is the geometric mean of the root
+    // moduli, the offset keeps the guesses off the real axis.
+    let radius = match (coefficients[0] / coefficients[n])
+        .abs()
+        .powf(1.0 / n as f64)
+    {
+        r if r.is_normal() => r,
+        _ => 1.0,
+    };
+    let mut z = (0..n)
+        .map(|k| Complex64::from_polar(radius, std::f64::consts::TAU * k as f64 / n as f64 + 0.4))
+        .collect::<Vec<_>>();
+    let mut accepted = vec![false; n];
+    for _ in 0..options.max_iterations {
+        for i in 0..n {
+            if accepted[i] {
+                continue;
+            }
+            let (value, slope, scale) = horner(coefficients, z[i]);
+            if value.norm() <= options.tolerance * scale {
+                accepted[i] = true;
+                continue;
+            }
+            let ratio = value / slope;
+            let repulsion = (0..n)
+                .filter(|j| *j != i)
+                .map(|j| (z[i] - z[j]).inv())
+                .sum::<Complex64>();
+            let step = ratio / (1.0 - ratio * repulsion);
+            if step.is_finite() {
+                z[i] -= step;
+                accepted[i] = step.norm() <= f64::EPSILON * z[i].norm();
+            } else {
+                // A critical point or a collision, nudge the approximation.
+                z[i] += Complex64::new(f64::EPSILON.sqrt(), f64::EPSILON.sqrt()) * radius;
+            }
+        }
+        if accepted.iter().all(|a| *a) {
+            return (z, true);
+        }
+    }
+    (z, false)
+}
+
+/// Whether approximations within `spread` of `centre` are explained by
+/// rounding error around a root of multiplicity `m`. A coefficient
+/// perturbation of relative size `eps` moves such a root by about
+/// `(eps s(|c|) / |p^(m)(c) / m!|)^(1/m)`, with `s` the rounding bound of
+/// [`horner`].
+fn is_repeated(coefficients: &[f64], centre: Complex64, spread: f64, m: u32) -> bool {
+    let (_, _, scale) = horner(coefficients, centre);
+    let mut derivative_m = coefficients.to_vec();
+    let mut factorial = 1.0;
+    for k in 1..=m {
+        derivative_m = derivative(&derivative_m);
+        factorial *= k as f64;
+    }
+    let (value, _, _) = horner(&derivative_m, centre);
+    let n = coefficients.len() as f64;
+    let perturbation =
+        (4.0 * n * f64::EPSILON * scale * factorial / value.norm()).powf(1.0 / m as f64);
+    spread <= 2.0 * perturbation
+}
+
+/// Groups the approximations into roots with multiplicities.
+///
+/// Each approximation `z_i` is the centre of a disc of radius
+/// `n |p(z_i)| / |a_n prod_{j != i} (z_i - z_j)|`, a connected union of `m`
+/// such discs contains `m` roots. Repeated roots are only found to about
+/// `eps^(1/m)`, their approximations spread out and the discs overlap. An
+/// overlap that is too wide for a repeated root only shows the roots are
+/// ill-conditioned, the approximations are then kept as simple roots.
+fn clusters(coefficients: &[f64], z: &[Complex64]) -> Vec<(Complex64, f64, u32)> {
+    let n = z.len();
+    let lead = coefficients[n];
+    let radii = (0..n)
+        .map(|i| {
+            let (value, _, _) = horner(coefficients, z[i]);
+            let product = (0..n)
+                .filter(|j| *j != i)
+                .map(|j| z[i] - z[j])
+                .product::<Complex64>();
+            match (product * lead).norm() {
+                0.0 => 0.0,
+                d => n as f64 * value.norm() / d,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = (0..n).collect::<Vec<_>>();
+    fn find(group: &mut [usize], i: usize) -> usize {
+        match group[i] {
+            g if g == i => i,
+            g => {
+                let root = find(group, g);
+                group[i] = root;
+                root
+            }
+        }
+    }
+    for i in 0..n {
+        for j in i + 1..n {
+            if (z[i] - z[j]).norm() <= radii[i] + radii[j] {
+                let (a, b) = (find(&mut group, i), find(&mut group, j));
+                group[a] = b;
+            }
+        }
+    }
+
+    let mut result: Vec<(usize, Vec<usize>)> = Vec::new();
+    for i in 0..n {
+        let g = find(&mut group, i);
+        match result.iter_mut().find(|(key, _)| *key == g) {
+            Some((_, members)) => members.push(i),
+            None => result.push((g, vec![i])),
+        }
+    }
+    result
+        .into_iter()
+        .flat_map(|(_, members)| {
+            let m = members.len() as u32;
+            let centre = members.iter().map(|i| z[*i]).sum::<Complex64>() / m as f64;
+            let spread = members
+                .iter()
+                .map(|i| (z[*i] - centre).norm())
+                .fold(0.0, f64::max);
+            match m == 1 || is_repeated(coefficients, centre, spread, m) {
+                true => {
+                    let radius = members
+                        .iter()
+                        .map(|i| (z[*i] - centre).norm() + radii[*i])
+                        .fold(0.0, f64::max);
+                    vec![(centre, radius, m)]
+                }
+                false => members.iter().map(|i| (z[*i], radii[*i], 1)).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Newton's method on `p^(m - 1)`, which has a simple root where `p` has
+/// one of multiplicity `m`. The iteration stops before moving further than
+/// `reach` from the start, so that it cannot jump to a neighbouring root.
+fn polish(
+    coefficients: &[f64],
+    start: Complex64,
+    multiplicity: u32,
+    reach: f64,
+    iterations: usize,
+) -> Complex64 {
+    let mut coefficients = coefficients.to_vec();
+    for _ in 1..multiplicity {
+        coefficients = derivative(&coefficients);
+    }
+    let mut root = start;
+    let mut previous = f64::INFINITY;
+    for _ in 0..iterations {
+        let (value, slope, _) = horner(&coefficients, root);
+        let step = value / slope;
+        if !step.is_finite() || step.norm() >= previous || (root - step - start).norm() > reach {
+            break;
+        }
+        root -= step;
+        previous = step.norm();
+        if previous <= f64::EPSILON * root.norm() {
+            break;
+        }
+    }
+    root
+}
+
+/// Makes the complex roots exact conjugate pairs, as they are for real
+/// coefficients. Each root in the upper half plane is paired with the
+/// nearest mirrored root of the same multiplicity, both take the mean of
+/// their real parts and of their imaginary parts' moduli. Without this the
+/// real parts of a pair differ by rounding error, which decides their order.
+fn pair_conjugates(roots: &mut [Root]) {
+    let mut paired = vec![false; roots.len()];
+    for i in 0..roots.len() {
+        if paired[i] || roots[i].value.im <= 0.0 {
+            continue;
+        }
+        let partner = (0..roots.len())
+            .filter(|j| {
+                !paired[*j]
+                    && roots[*j].value.im < 0.0
+                    && roots[*j].multiplicity == roots[i].multiplicity
+            })
+            .min_by(|a, b| {
+                let distance = |j: usize| (roots[j].value.conj() - roots[i].value).norm();
+                distance(*a).total_cmp(&distance(*b))
+            });
+        if let Some(j) = partner {
+            let (a, b) = (roots[i].value, roots[j].value);
+            let value = Complex64::new((a.re + b.re) / 2.0, (a.im - b.im) / 2.0);
+            roots[i].value = value;
+            roots[j].value = value.conj();
+            paired[i] = true;
+            paired[j] = true;
+        }
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + Float,
+{
+    /// All real and complex roots, see [`Polynomial::roots_with`].
+    pub fn roots(&self) -> Result<Vec<Root>, RootError> {
+        self.roots_with(&RootOptions::default())
+    }
+
+    /// All real and complex roots with their multiplicities, ordered by
+    /// real and then imaginary part. The multiplicities sum to the degree.
+    ///
+    /// The roots are approximated simultaneously by the Aberth–Ehrlich
+    /// method, approximations that coincide within their error bounds are
+    /// merged into a repeated root, and each root is refined by Newton's
+    /// method. Roots whose error disc meets the real axis are made exactly
+    /// real, the others are made exact conjugate pairs.
+    pub fn roots_with(&self, options: &RootOptions) -> Result<Vec<Root>, RootError> {
+        let mut coefficients = self
+            .poly
+            .iter()
+            .map(|c| c.to_f64().unwrap())
+            .collect::<Vec<f64>>();
+        if coefficients.iter().any(|c| !c.is_finite()) {
+            return Err(RootError::NonFinite);
+        }
+        while coefficients.last().is_some_and(|c| *c == 0.0) {
+            coefficients.pop();
+        }
+        if coefficients.is_empty() {
+            return Err(RootError::ZeroPolynomial);
+        }
+
+        let mut roots = Vec::new();
+        let zeros = coefficients.iter().take_while(|c| **c == 0.0).count();
+        if zeros > 0 {
+            roots.push(Root {
+                value: Complex64::zero(),
+                multiplicity: zeros as u32,
+            });
+            coefficients.drain(..zeros);
+        }
+        let converged = match coefficients.len() {
+            1 => true,
+            _ => {
+                let (z, converged) = aberth(&coefficients, options);
+                let clusters = clusters(&coefficients, &z);
+                roots.extend(clusters.iter().map(|(centre, radius, multiplicity)| {
+                    let reach = clusters
+                        .iter()
+                        .map(|(other, _, _)| (other - centre).norm())
+                        .filter(|distance| *distance > 0.0)
+                        .fold(f64::INFINITY, f64::min)
+                        / 2.0;
+                    let centre = match centre.im.abs() <= *radius {
+                        true => Complex64::new(centre.re, 0.0),
+                        false => *centre,
+                    };
+                    Root {
+                        value: polish(
+                            &coefficients,
+                            centre,
+                            *multiplicity,
+                            reach,
+                            options.polish_iterations,
+                        ),
+                        multiplicity: *multiplicity,
+                    }
+                }));
+                converged
+            }
+        };
+        pair_conjugates(&mut roots);
+        roots.sort_by(|a, b| {
+            a.value
+                .re
+                .total_cmp(&b.value.re)
+                .then(a.value.im.total_cmp(&b.value.im))
+        });
+        match converged {
+            true => Ok(roots),
+            false => Err(RootError::NotConverged(roots)),
+        }
+    }
+
+    /// The real roots in increasing order, see [`Polynomial::roots`].
+    pub fn real_roots(&self) -> Result<Vec<Root<f64>>, RootError> {
+        self.real_roots_with(&RootOptions::default())
+    }
+
+    /// The real roots for the given options, see [`Polynomial::roots_with`].
+    ///
+    /// A root counts as real when its imaginary part is exactly zero. This
+    /// relies on `roots_with` snapping a root onto the real axis when its
+    /// error disc meets it, a real root is otherwise only found with an
+    /// imaginary part of the size of the rounding error.
+    pub fn real_roots_with(&self, options: &RootOptions) -> Result<Vec<Root<f64>>, RootError> {
+        Ok(self
+            .roots_with(options)?
+            .into_iter()
+            .filter(|root| root.value.im == 0.0)
+            .map(|root| Root {
+                value: root.value.re,
+                multiplicity: root.multiplicity,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,370 @@
+use std::f64::consts::{E, FRAC_2_SQRT_PI, PI, SQRT_2};
+use std::fmt::Display;
+
+use crate::math::{
+    expression::Expression, func_traits::VariableFunction, function_type::FunctionType,
+    render::Renderer,
+};
+
+/// Special functions, evaluated in pure Rust. Accuracy, as relative error
+/// unless stated otherwise:
+///
+/// - `Gamma`, `LogGamma`: Lanczos approximation, about `1e-13`.
+/// - `Polygamma`: recurrence and asymptotic series, about `1e-14`.
+/// - `Erf`, `Erfc`, `NormalCdf`: power series below 2 and a continued
+///   fraction above, about `1e-14`. `Erfc` underflows to zero above 26.
+/// - Bessel functions: power series up to 14 and Hankel's asymptotic
+///   expansion above, about `1e-11` absolute.
+/// - `LambertW`: Halley iteration to machine precision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecialFunction {
+    Gamma,
+    /// `ln|Gamma(x)|`.
+    LogGamma,
+    /// `n`-th derivative of the digamma function `Gamma' / Gamma`,
+    /// `Polygamma(0)` is the digamma function itself.
+    Polygamma(u32),
+    Erf,
+    Erfc,
+    /// Bessel function of the first kind of order zero.
+    BesselJ0,
+    BesselJ1,
+    /// Bessel function of the second kind of order zero.
+    BesselY0,
+    BesselY1,
+    /// Principal branch `W0` of the Lambert W function, the inverse of
+    /// `w e^w` on `[-1/e, inf)`.
+    LambertW,
+    /// Cumulative distribution function of the standard normal
+    /// distribution.
+    NormalCdf,
+}
+
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+impl Display for SpecialFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&Renderer::plain(None).special(self))
+    }
+}
+
+impl SpecialFunction {
+    /// Applies the function to `argument`.
+    pub fn of(self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(FunctionType::Special(self), argument.into())
+    }
+
+    /// Whether `x` lies in the domain, outside of it `evaluate` is NaN or
+    /// infinite.
+    pub fn domain_contains(&self, x: f64) -> bool {
+        match self {
+            Self::Gamma | Self::LogGamma | Self::Polygamma(_) => !(x <= 0.0 && x.fract() == 0.0),
+            Self::BesselY0 | Self::BesselY1 => x > 0.0,
+            Self::LambertW => x >= -E.recip(),
+            _ => !x.is_nan(),
+        }
+    }
+}
+
+impl VariableFunction for SpecialFunction {
+    type Derivative = FunctionType;
+
+    fn evaluate(&self, x: f64) -> f64 {
+        match self {
+            Self::Gamma => gamma(x),
+            Self::LogGamma => log_gamma(x),
+            Self::Polygamma(n) => polygamma(*n, x),
+            Self::Erf => erf(x),
+            Self::Erfc => erfc(x),
+            Self::BesselJ0 => bessel_j(0, x),
+            Self::BesselJ1 => bessel_j(1, x),
+            Self::BesselY0 => bessel_y(0, x),
+            Self::BesselY1 => bessel_y(1, x),
+            Self::LambertW => lambert_w(x),
+            Self::NormalCdf => 0.5 * erfc(-x / SQRT_2),
+        }
+    }
+
+    /// The derivative as a lambda, e.g. `2/sqrt(pi) exp(-x^2)` for the
+    /// error function.
+    fn derivative(&self) -> FunctionType {
+        let x = Expression::variable("x");
+        let gaussian =
+            |scale: f64, exponent: f64| scale * FunctionType::Exponential.of(exponent * (&x * &x));
+        FunctionType::Lambda(Box::new(match self {
+            Self::Gamma => Self::Gamma.of(x.clone()) * Self::Polygamma(0).of(x),
+            Self::LogGamma => Self::Polygamma(0).of(x),
+            Self::Polygamma(n) => Self::Polygamma(n + 1).of(x),
+            Self::Erf => gaussian(FRAC_2_SQRT_PI, -1.0),
+            Self::Erfc => gaussian(-FRAC_2_SQRT_PI, -1.0),
+            Self::NormalCdf => gaussian((2.0 * PI).sqrt().recip(), -0.5),
+            Self::BesselJ0 => -Self::BesselJ1.of(x),
+            Self::BesselY0 => -Self::BesselY1.of(x),
+            // J1' = J0 - J1 / x, likewise for Y1.
+            Self::BesselJ1 | Self::BesselY1 => {
+                let (zero, one) = match self {
+                    Self::BesselJ1 => (Self::BesselJ0, Self::BesselJ1),
+                    _ => (Self::BesselY0, Self::BesselY1),
+                };
+                zero.of(x.clone()) - one.of(x.clone()) * FunctionType::Power(-1.0).of(x)
+            }
+            // W' = 1 / (x + e^W), which unlike W / (x (1 + W)) holds at 0.
+            Self::LambertW => FunctionType::Power(-1.0)
+                .of(&x + FunctionType::Exponential.of(Self::LambertW.of(x.clone()))),
+        }))
+    }
+}
+
+/// Lanczos coefficients for `g = 7`.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Lanczos sum and `t = x + g + 0.5` for `Gamma(x + 1)`.
+fn lanczos(x: f64) -> (f64, f64) {
+    let sum = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    (sum, x + 7.5)
+}
+
+fn gamma(x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        return f64::NAN;
+    }
+    if x < 0.5 {
+        // Reflection: Gamma(x) Gamma(1 - x) = pi / sin(pi x)
+        return PI / ((PI * x).sin() * gamma(1.0 - x));
+    }
+    let (sum, t) = lanczos(x - 1.0);
+    // t^(x - 0.5) is split in two so that it does not overflow early.
+    let power = t.powf((x - 0.5) / 2.0);
+    (2.0 * PI).sqrt() * power * (power * (-t).exp()) * sum
+}
+
+fn log_gamma(x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        return f64::INFINITY;
+    }
+    if x < 0.5 {
+        return (PI / (PI * x).sin().abs()).ln() - log_gamma(1.0 - x);
+    }
+    let (sum, t) = lanczos(x - 1.0);
+    0.5 * (2.0 * PI).ln() + (x - 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Bernoulli numbers `B_2` to `B_16`.
+const BERNOULLI: [f64; 8] = [
+    1.0 / 6.0,
+    -1.0 / 30.0,
+    1.0 / 42.0,
+    -1.0 / 30.0,
+    5.0 / 66.0,
+    -691.0 / 2730.0,
+    7.0 / 6.0,
+    -3617.0 / 510.0,
+];
+
+fn polygamma(n: u32, x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        return f64::NAN;
+    }
+    if n == 0 && x < 0.0 {
+        // Reflection: psi(1 - x) - psi(x) = pi cot(pi x)
+        return polygamma(0, 1.0 - x) - PI / (PI * x).tan();
+    }
+    // psi^(n)(x) = psi^(n)(x + 1) - (-1)^n n! / x^(n + 1), shifting x up
+    // to where the asymptotic series is accurate.
+    let n_factorial: f64 = (1..=n).map(f64::from).product();
+    let sign = if n % 2 == 1 { 1.0 } else { -1.0 };
+    let mut x = x;
+    let mut shift = 0.0;
+    while x < 15.0 + n as f64 {
+        shift += sign * n_factorial / x.powi(n as i32 + 1);
+        x += 1.0;
+    }
+    let series = if n == 0 {
+        x.ln()
+            - 0.5 / x
+            - BERNOULLI
+                .iter()
+                .enumerate()
+                .map(|(k, b)| b / ((2 * k + 2) as f64 * x.powi(2 * k as i32 + 2)))
+                .sum::<f64>()
+    } else {
+        // (-1)^(n + 1) [(n - 1)! / x^n + n! / (2 x^(n + 1))
+        //     + sum B_2k (2k + n - 1)! / ((2k)! x^(2k + n))]
+        let n_i = n as i32;
+        let terms = BERNOULLI
+            .iter()
+            .enumerate()
+            .map(|(k, b)| {
+                let k = 2 * k as u32 + 2;
+                let ratio: f64 = (k + 1..k + n).map(f64::from).product();
+                b * ratio / x.powi(k as i32 + n_i)
+            })
+            .sum::<f64>();
+        sign * (n_factorial / n as f64 / x.powi(n_i)
+            + n_factorial / (2.0 * x.powi(n_i + 1))
+            + terms)
+    };
+    series + shift
+}
+
+fn erf(x: f64) -> f64 {
+    if x.abs() >= 2.0 {
+        return x.signum() * (1.0 - erfc(x.abs()));
+    }
+    // erf(x) = 2/sqrt(pi) sum (-1)^n x^(2n + 1) / (n! (2n + 1))
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..60 {
+        term *= -x * x / n as f64;
+        let contribution = term / (2 * n + 1) as f64;
+        sum += contribution;
+        if contribution.abs() < 1e-17 * sum.abs() {
+            break;
+        }
+    }
+    FRAC_2_SQRT_PI * sum
+}
+
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    if x < 2.0 {
+        return 1.0 - erf(x);
+    }
+    // erfc(x) = e^(-x^2) / sqrt(pi) / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...)))),
+    // evaluated from the back.
+    let fraction = (1..80).rev().fold(x, |tail, k| x + k as f64 / 2.0 / tail);
+    (-x * x).exp() / PI.sqrt() / fraction
+}
+
+/// `J_n(x)` for `n` zero or one.
+fn bessel_j(n: i32, x: f64) -> f64 {
+    if x < 0.0 {
+        return if n == 0 {
+            bessel_j(0, -x)
+        } else {
+            -bessel_j(1, -x)
+        };
+    }
+    if x >= 14.0 {
+        return hankel(n, x).0;
+    }
+    // J_n(x) = sum (-1)^k (x/2)^(2k + n) / (k! (k + n)!)
+    let half = x / 2.0;
+    let mut term = half.powi(n);
+    let mut sum = term;
+    for k in 1..80 {
+        term *= -half * half / (k * (k + n)) as f64;
+        sum += term;
+        if term.abs() < 1e-17 * sum.abs().max(1e-300) {
+            break;
+        }
+    }
+    sum
+}
+
+/// `Y_n(x)` for `n` zero or one.
+fn bessel_y(n: i32, x: f64) -> f64 {
+    if x <= 0.0 {
+        return f64::NAN;
+    }
+    if x >= 14.0 {
+        return hankel(n, x).1;
+    }
+    let half = x / 2.0;
+    let log = 2.0 / PI * half.ln() * bessel_j(n, x);
+    // sum over k of (psi(k + 1) + psi(k + n + 1)) (-1)^k (x/2)^(2k + n) / (k! (k + n)!),
+    // with psi(k + 1) = H_k - euler_gamma.
+    let mut term = half.powi(n);
+    let mut harmonic = [0.0, if n == 0 { 0.0 } else { 1.0 }];
+    let mut sum = term * (harmonic[0] + harmonic[1] - 2.0 * EULER_GAMMA);
+    for k in 1..80 {
+        term *= -half * half / (k * (k + n)) as f64;
+        harmonic[0] += 1.0 / k as f64;
+        harmonic[1] += 1.0 / (k + n) as f64;
+        let contribution = term * (harmonic[0] + harmonic[1] - 2.0 * EULER_GAMMA);
+        sum += contribution;
+        if contribution.abs() < 1e-17 * sum.abs().max(1e-300) {
+            break;
+        }
+    }
+    match n {
+        0 => log - 2.0 / PI * sum / 2.0,
+        _ => log - 2.0 / (PI * x) - sum / PI,
+    }
+}
+
+/// `(J_n(x), Y_n(x))` from Hankel's asymptotic expansion, for large `x`.
+fn hankel(n: i32, x: f64) -> (f64, f64) {
+    let mu = 4.0 * (n * n) as f64;
+    let (mut p, mut q) = (0.0, 0.0);
+    let mut term = 1.0;
+    for k in 0..30 {
+        if k > 0 {
+            let next = term * (mu - ((2 * k - 1) * (2 * k - 1)) as f64) / (k as f64 * 8.0 * x);
+            if next.abs() > term.abs() {
+                break;
+            }
+            term = next;
+        }
+        match k % 4 {
+            0 => p += term,
+            1 => q += term,
+            2 => p -= term,
+            _ => q -= term,
+        }
+    }
+    let chi = x - (n as f64 / 2.0 + 0.25) * PI;
+    let scale = (2.0 / (PI * x)).sqrt();
+    (
+        scale * (p * chi.cos() - q * chi.sin()),
+        scale * (p * chi.sin() + q * chi.cos()),
+    )
+}
+
+fn lambert_w(x: f64) -> f64 {
+    let branch_point = -E.recip();
+    if x.is_nan() || x < branch_point {
+        return f64::NAN;
+    }
+    if x == branch_point {
+        return -1.0;
+    }
+    if x == 0.0 {
+        return 0.0;
+    }
+    let mut w = if x < -0.3 {
+        // Series around the branch point in p = sqrt(2 (e x + 1)).
+        let p = (2.0 * (E * x + 1.0)).sqrt();
+        -1.0 + p - p * p / 3.0 + 11.0 / 72.0 * p * p * p
+    } else if x < 3.0 {
+        x.ln_1p()
+    } else {
+        let (l1, l2) = (x.ln(), x.ln().ln());
+        l1 - l2 + l2 / l1
+    };
+    for _ in 0..50 {
+        let e = w.exp();
+        let f = w * e - x;
+        let step = f / (e * (w + 1.0) - (w + 2.0) * f / (2.0 * w + 2.0));
+        w -= step;
+        if step.abs() <= 4.0 * f64::EPSILON * (1.0 + w.abs()) {
+            break;
+        }
+    }
+    w
+}
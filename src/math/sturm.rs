@@ -0,0 +1,197 @@
+use num_traits::ToPrimitive;
+
+use crate::math::polynomial::{Polynomial, PolynomialOperationTypes};
+
+/// Sign of the polynomial at `x`, infinite `x` gives the sign at the
+/// respective end of the real line.
+fn sign_at(polynomial: &Polynomial<f64>, x: f64) -> f64 {
+    match x.is_infinite() {
+        true => {
+            let lead = polynomial.poly[polynomial.deg as usize];
+            match polynomial.deg % 2 == 1 && x < 0.0 {
+                true => -lead.signum(),
+                false => lead.signum(),
+            }
+        }
+        false => match polynomial.evaluate(x) {
+            0.0 => 0.0,
+            value => value.signum(),
+        },
+    }
+}
+
+/// Number of sign changes in `values`, zeros are skipped.
+fn sign_changes(values: impl Iterator<Item = f64>) -> usize {
+    values
+        .filter(|value| *value != 0.0)
+        .fold(
+            (0, None),
+            |(changes, last): (usize, Option<f64>), value| match last
+                .is_some_and(|last| last * value < 0.0)
+            {
+                true => (changes + 1, Some(value)),
+                false => (changes, Some(value)),
+            },
+        )
+        .0
+}
+
+impl<T> Polynomial<T>
+where
+    T: PolynomialOperationTypes + Copy + ToPrimitive,
+{
+    fn promoted(&self) -> Polynomial<f64> {
+        Polynomial {
+            poly: self.poly.iter().map(|c| c.to_f64().unwrap()).collect(),
+            deg: self.deg,
+        }
+        .normalized()
+    }
+
+    /// `p, p', -rem(p, p'), ...` for the square-free part
+    /// `p = self / gcd(self, self')`, each entry the negated remainder of
+    /// the division of the two before it, up to the last non-zero one.
+    /// Empty for the zero polynomial.
+    ///
+    /// Removing repeated factors keeps the counts valid when a bound is a
+    /// root, the square-free part and the remainders are only chopped at the
+    /// level of rounding error.
+    /// The GCD uses [`Polynomial::DEFAULT_TOLERANCE`], so roots closer than
+    /// about its square root, `1e-4` relative, count as one repeated root.
+    pub fn sturm_sequence(&self) -> Vec<Polynomial<f64>> {
+        let p = self.promoted();
+        if p.is_zero() {
+            return Vec::new();
+        }
+        let tolerance = 16.0 * f64::EPSILON;
+        let p = (&p / &p.gcd(&p.derivative())).trimmed(tolerance);
+        let mut sequence = vec![p.derivative(), p];
+        sequence.reverse();
+        while !sequence[sequence.len() - 1].is_zero() {
+            let n = sequence.len();
+            let (_, remainder) = sequence[n - 2]
+                .div_rem_with_tolerance(&sequence[n - 1], tolerance)
+                .unwrap();
+            sequence.push(remainder * -1.0);
+        }
+        sequence.pop();
+        sequence
+    }
+
+    /// Number of distinct real roots in `(a, b]`, by Sturm's theorem. The
+    /// bounds may be infinite, the zero polynomial has no isolated roots
+    /// and gives zero.
+    ///
+    /// The sequence and its signs are computed in `f64`, so the count is an
+    /// estimate and not a certificate. Rounding can drop or merge roots of
+    /// ill-conditioned polynomials and misjudge the sign at a bound very
+    /// close to a root. Certified counts would need exact rational
+    /// arithmetic, which this crate does not provide.
+    pub fn count_real_roots(&self, a: f64, b: f64) -> usize {
+        Self::count_with(&self.sturm_sequence(), a, b)
+    }
+
+    fn count_with(sequence: &[Polynomial<f64>], a: f64, b: f64) -> usize {
+        let changes = |x: f64| sign_changes(sequence.iter().map(|p| sign_at(p, x)));
+        changes(a).saturating_sub(changes(b))
+    }
+
+    /// Upper bounds on the number of positive and of negative roots by
+    /// Descartes' rule of signs, each counted with multiplicity and exact
+    /// up to an even number.
+    pub fn descartes_rule(&self) -> (usize, usize) {
+        let p = self.promoted();
+        let positive = sign_changes(p.poly.iter().copied());
+        let negative = sign_changes(p.poly.iter().enumerate().map(|(k, c)| match k % 2 {
+            0 => *c,
+            _ => -c,
+        }));
+        (positive, negative)
+    }
+
+    /// Cauchy's bound `1 + max |a_k / a_n|`, every root has a smaller
+    /// modulus. Infinite for the zero polynomial.
+    pub fn cauchy_bound(&self) -> f64 {
+        let p = self.promoted();
+        let lead = p.poly[p.deg as usize];
+        match lead {
+            0.0 => f64::INFINITY,
+            _ => {
+                1.0 + p.poly[..p.deg as usize]
+                    .iter()
+                    .fold(0.0, |m: f64, c| m.max((c / lead).abs()))
+            }
+        }
+    }
+
+    /// Fujiwara's bound `2 max |a_(n-k) / a_n|^(1/k)` with the constant
+    /// term halved, every root has at most this modulus. Usually tighter
+    /// than [`Polynomial::cauchy_bound`].
+    pub fn fujiwara_bound(&self) -> f64 {
+        let p = self.promoted();
+        let n = p.deg as usize;
+        let lead = p.poly[n];
+        match lead {
+            0.0 => f64::INFINITY,
+            _ => {
+                2.0 * (1..=n)
+                    .map(|k| {
+                        let c = match k {
+                            k if k == n => p.poly[0] / 2.0,
+                            _ => p.poly[n - k],
+                        };
+                        (c / lead).abs().powf(1.0 / k as f64)
+                    })
+                    .fold(0.0, f64::max)
+            }
+        }
+    }
+
+    /// Disjoint intervals `(a, b]` in increasing order, each containing
+    /// exactly one distinct real root.
+    ///
+    /// Found by bisecting a root bound with [`Polynomial::count_real_roots`],
+    /// roots closer than the floating point resolution share an interval.
+    /// The intervals are only as reliable as the floating point counts.
+    pub fn isolate_real_roots(&self) -> Vec<(f64, f64)> {
+        let sequence = self.sturm_sequence();
+        if sequence.is_empty() {
+            return Vec::new();
+        }
+        let bound = match self.cauchy_bound().min(self.fujiwara_bound()) {
+            0.0 => 1.0,
+            bound => 2.0 * bound,
+        };
+        let mut pending = vec![(-bound, bound)];
+        let mut intervals = Vec::new();
+        while let Some((a, b)) = pending.pop() {
+            let middle = a + (b - a) / 2.0;
+            match Self::count_with(&sequence, a, b) {
+                0 => {}
+                1 => intervals.push((a, b)),
+                _ if middle <= a || middle >= b => intervals.push((a, b)),
+                _ => pending.extend([(a, middle), (middle, b)]),
+            }
+        }
+        intervals.sort_by(|x, y| x.0.total_cmp(&y.0));
+        intervals
+    }
+
+    /// Bisects an interval `(a, b]` from [`Polynomial::isolate_real_roots`]
+    /// until it is at most `width` wide, keeping the root inside.
+    pub fn refine_real_root(&self, interval: (f64, f64), width: f64) -> (f64, f64) {
+        let sequence = self.sturm_sequence();
+        let (mut a, mut b) = interval;
+        while b - a > width {
+            let middle = a + (b - a) / 2.0;
+            if middle <= a || middle >= b {
+                break;
+            }
+            match Self::count_with(&sequence, a, middle) {
+                0 => a = middle,
+                _ => b = middle,
+            }
+        }
+        (a, b)
+    }
+}
@@ -1,4 +1,9 @@
+use std::fmt::Display;
+
+use crate::math::expression::Expression;
 use crate::math::func_traits::VariableFunction;
+use crate::math::function_type::FunctionType;
+use crate::math::render::Renderer;
 
 /// Function involving normal trigonometry
 #[derive(Debug, Clone, PartialEq)]
@@ -9,95 +14,168 @@ pub enum TrigonometricFunction {
     Cotangent,
     Secant,
     Cosecant,
-    Composite(Vec<TrigonometricFunction>),
-    Negative(Box<TrigonometricFunction>)
+    /// `coefficient * f1^n1 * f2^n2 * ...` for distinct base functions
+    /// `fi` of the same argument, e.g. `2 sec^2 tan` as
+    /// `Product(2.0, [(Secant, 2), (Tangent, 1)])`.
+    Product(f64, Vec<(TrigonometricFunction, u32)>),
+    /// Sum of base functions and products, closed under differentiation.
+    Sum(Vec<TrigonometricFunction>),
 }
 
+/// A product `coefficient * f1^n1 * ...` of base functions, in the order
+/// of [`TrigonometricFunction::base_index`].
+pub type Term = (f64, Vec<(TrigonometricFunction, u32)>);
+
+/// Product of the given functions, e.g. `composite!(sec!(), sec!(), tan!())`
+/// for `sec^2 tan`.
+#[macro_export]
 macro_rules! composite {
     ($($e:expr),*) => {{
-        TrigonometricFunction::Composite(vec![$($e),*])
+        $crate::math::trigonometric::TrigonometricFunction::product(vec![$($e),*])
     }}
 }
-
+#[macro_export]
 macro_rules! sin {
     () => {
-        TrigonometricFunction::Sine
+        $crate::math::trigonometric::TrigonometricFunction::Sine
     };
 }
 
+#[macro_export]
 macro_rules! cos {
     () => {
-        TrigonometricFunction::Cosine
+        $crate::math::trigonometric::TrigonometricFunction::Cosine
     };
 }
 
+#[macro_export]
 macro_rules! tan {
     () => {
-        TrigonometricFunction::Tangent
+        $crate::math::trigonometric::TrigonometricFunction::Tangent
     };
 }
 
+#[macro_export]
 macro_rules! cot {
     () => {
-        TrigonometricFunction::Cotangent
+        $crate::math::trigonometric::TrigonometricFunction::Cotangent
     };
 }
 
+#[macro_export]
 macro_rules! sec {
     () => {
-        TrigonometricFunction::Secant
+        $crate::math::trigonometric::TrigonometricFunction::Secant
     };
 }
 
+#[macro_export]
 macro_rules! cosec {
     () => {
-        TrigonometricFunction::Cosecant
+        $crate::math::trigonometric::TrigonometricFunction::Cosecant
     };
 }
 
+#[macro_export]
 macro_rules! neg {
     ($e:expr) => {
-        TrigonometricFunction::Negative(Box::new($e))
+        $crate::math::trigonometric::TrigonometricFunction::negate(&$e)
     };
 }
 
+impl Display for TrigonometricFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&Renderer::plain(None).trigonometric(self, None).0)
+    }
+}
+
 impl TrigonometricFunction {
-    pub fn to_string(&self) -> String {
+    /// Applies the function to `argument`, e.g. `sin!().of(x!())`.
+    pub fn of(self, argument: impl Into<Expression>) -> Expression {
+        Expression::apply(FunctionType::Trigonometric(self), argument.into())
+    }
+
+    /// Position of a base function in the canonical order of factors,
+    /// `None` for products and sums.
+    pub fn base_index(&self) -> Option<usize> {
         match self {
-            Self::Sine => "sin".to_string(),
-            Self::Cosine => "cos".to_string(),
-            Self::Tangent => "tan".to_string(),
-            Self::Cotangent => "cot".to_string(),
-            Self::Secant => "sec".to_string(),
-            Self::Cosecant => "cosec".to_string(),
-            Self::Composite(_) => "".to_string(),
-            Self::Negative(ref value) => format!("-{}", value.to_string())
+            Self::Sine => Some(0),
+            Self::Cosine => Some(1),
+            Self::Tangent => Some(2),
+            Self::Cotangent => Some(3),
+            Self::Secant => Some(4),
+            Self::Cosecant => Some(5),
+            Self::Product(..) | Self::Sum(_) => None,
         }
     }
 
-    fn flatten_composite(&self) -> Self {
+    /// Expands the function into a sum of products of base functions.
+    pub fn terms(&self) -> Vec<Term> {
         match self {
-            Self::Composite(ref comp) => {
-                let mut new_composite: Vec<TrigonometricFunction> = Vec::new();
-                comp.iter().for_each(|item| {
-                    item.flatten_composite();
-                    match item {
-                        Self::Composite(another_comp) => {
-                            for val in another_comp {
-                                new_composite.push(val.clone());
-                            }
-                        }
-                        _ => { }
-                    }
-                });
-                Self::Composite(new_composite)
+            Self::Product(coefficient, ref factors) => vec![(*coefficient, factors.clone())],
+            Self::Sum(ref terms) => terms.iter().flat_map(|t| t.terms()).collect(),
+            base => vec![(1.0, vec![(base.clone(), 1)])],
+        }
+    }
+
+    /// Builds the function from `terms`, sorting factors, combining like
+    /// terms and dropping zero ones.
+    pub fn from_terms(terms: impl IntoIterator<Item = Term>) -> Self {
+        let mut combined: Vec<Term> = Vec::new();
+        for (coefficient, factors) in terms {
+            let mut merged: Vec<(TrigonometricFunction, u32)> = Vec::new();
+            for (base, count) in factors.into_iter().filter(|(_, count)| *count > 0) {
+                match merged.iter_mut().find(|(b, _)| *b == base) {
+                    Some((_, c)) => *c += count,
+                    None => merged.push((base, count)),
+                }
+            }
+            merged.sort_by_key(|(base, _)| base.base_index());
+            match combined.iter_mut().find(|(_, f)| *f == merged) {
+                Some((c, _)) => *c += coefficient,
+                None => combined.push((coefficient, merged)),
             }
-            _ => { self.clone() }
         }
+        combined.retain(|(coefficient, _)| *coefficient != 0.0);
+
+        let single = |(coefficient, mut factors): Term| match (coefficient, factors.as_slice()) {
+            (1.0, [(_, 1)]) => factors.pop().unwrap().0,
+            _ => Self::Product(coefficient, factors),
+        };
+        match combined.len() {
+            0 => Self::Product(0.0, Vec::new()),
+            1 => single(combined.pop().unwrap()),
+            _ => Self::Sum(combined.into_iter().map(single).collect()),
+        }
+    }
+
+    /// Product of `functions`, distributed over sums.
+    pub fn product(functions: Vec<TrigonometricFunction>) -> Self {
+        let terms = functions.iter().fold(vec![(1.0, Vec::new())], |terms, f| {
+            terms
+                .iter()
+                .flat_map(|(c, factors)| {
+                    f.terms().into_iter().map(move |(d, other)| {
+                        (c * d, factors.iter().cloned().chain(other).collect())
+                    })
+                })
+                .collect::<Vec<Term>>()
+        });
+        Self::from_terms(terms)
+    }
+
+    pub fn negate(&self) -> Self {
+        self.scale(-1.0)
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::from_terms(self.terms().into_iter().map(|(c, f)| (factor * c, f)))
     }
 }
 
 impl VariableFunction for TrigonometricFunction {
+    type Derivative = Self;
+
     fn evaluate(&self, x: f64) -> f64 {
         match self {
             Self::Sine => x.sin(),
@@ -106,12 +184,10 @@ impl VariableFunction for TrigonometricFunction {
             Self::Cotangent => 1.0 / x.tan(),
             Self::Secant => 1.0 / x.cos(),
             Self::Cosecant => 1.0 / x.sin(),
-            Self::Composite(ref value) => value
+            Self::Product(coefficient, ref factors) => factors
                 .iter()
-                .map(|f| f.evaluate(x))
-                .reduce(|v, c| v * c)
-                .unwrap(),
-            Self::Negative(ref value) => -value.evaluate(x)
+                .fold(*coefficient, |v, (f, n)| v * f.evaluate(x).powi(*n as i32)),
+            Self::Sum(ref terms) => terms.iter().map(|t| t.evaluate(x)).sum(),
         }
     }
 
@@ -123,8 +199,27 @@ impl VariableFunction for TrigonometricFunction {
             Self::Cotangent => neg!(composite!(cosec!(), cosec!())),
             Self::Secant => composite!(sec!(), tan!()),
             Self::Cosecant => neg!(composite!(cosec!(), cot!())),
-            Self::Negative(ref value) => neg!(value.derivative()),
-            _ => cos!()
+            // Product rule, (c f^n g)' = c n f^(n - 1) f' g + c f^n g'
+            Self::Product(..) | Self::Sum(_) => {
+                Self::from_terms(self.terms().into_iter().flat_map(|(coefficient, factors)| {
+                    (0..factors.len())
+                        .flat_map(|index| {
+                            let (ref base, count) = factors[index];
+                            let mut rest = factors.clone();
+                            rest[index].1 -= 1;
+                            base.derivative()
+                                .terms()
+                                .into_iter()
+                                .map(move |(c, derivative)| {
+                                    (
+                                        coefficient * count as f64 * c,
+                                        rest.iter().cloned().chain(derivative).collect(),
+                                    )
+                                })
+                        })
+                        .collect::<Vec<Term>>()
+                }))
+            }
         }
     }
 }
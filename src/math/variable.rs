@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::math::{
+    expression::Expression, expression_type::ExpressionType, func_traits::VariableFunction,
+    function_type::FunctionType,
+};
+
+/// Values bound to variable names, the anonymous variable of polynomials
+/// and [`VariableFunction::evaluate`] is bound as `x`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Env {
+    bindings: HashMap<String, f64>,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the environment with `name` bound to `value`.
+    pub fn with(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: f64) {
+        self.bindings.insert(name.into(), value);
+    }
+
+    pub fn get(&self, name: &str) -> Result<f64, EvaluationError> {
+        self.bindings
+            .get(name)
+            .copied()
+            .ok_or_else(|| EvaluationError::Unbound(name.to_string()))
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, f64)> for Env {
+    fn from_iter<I: IntoIterator<Item = (S, f64)>>(iter: I) -> Self {
+        Self {
+            bindings: iter
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvaluationError {
+    /// The expression refers to a variable without a value in the
+    /// environment.
+    Unbound(String),
+}
+
+impl Display for EvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unbound(name) => write!(f, "variable `{}` is not bound", name),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+impl Expression {
+    /// Evaluates the expression with the variables bound in `env`. Plain
+    /// [`VariableFunction::evaluate`] only binds `x` and is NaN on any
+    /// other variable.
+    pub fn evaluate_with(&self, env: &Env) -> Result<f64, EvaluationError> {
+        self.function
+            .evaluate_with(self.input.evaluate_with(env)?, env)
+    }
+}
+
+impl ExpressionType {
+    pub fn evaluate_with(&self, env: &Env) -> Result<f64, EvaluationError> {
+        Ok(match self {
+            Self::Constant(value) => *value,
+            Self::Variable(name) => env.get(name)?,
+            Self::Polynomial(ref value) => value.evaluate(env.get("x")?),
+            Self::Expressions(ref value) => value
+                .iter()
+                .map(|c| c.evaluate_with(env))
+                .sum::<Result<f64, EvaluationError>>()?,
+            Self::MultipliedExpressions(ref value) => value
+                .iter()
+                .map(|c| c.evaluate_with(env))
+                .product::<Result<f64, EvaluationError>>()?,
+            Self::DividedExpressions(num, den) => {
+                num.evaluate_with(env)? / den.evaluate_with(env)?
+            }
+            Self::Atan2(y, x) => y.evaluate_with(env)?.atan2(x.evaluate_with(env)?),
+            Self::Power(base, exponent) => {
+                base.evaluate_with(env)?.powf(exponent.evaluate_with(env)?)
+            }
+            Self::Piecewise(branches, otherwise) => {
+                for (condition, value) in branches {
+                    if condition.holds_with(env)? {
+                        return value.evaluate_with(env);
+                    }
+                }
+                otherwise.evaluate_with(env)?
+            }
+        })
+    }
+}
+
+impl FunctionType {
+    /// Applies the function to `value`, a lambda sees the other variables
+    /// of `env` and `value` as `x`.
+    pub fn evaluate_with(&self, value: f64, env: &Env) -> Result<f64, EvaluationError> {
+        match self {
+            Self::Lambda(ref expr) => expr.evaluate_with(&env.clone().with("x", value)),
+            _ => Ok(self.evaluate(value)),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use expression::math::{expression::Expression, func_traits::VariableFunction};
+
+/// Asserts that `input` integrates to an antiderivative whose derivative
+/// is `input` again.
+fn assert_antiderivative(input: &str) {
+    let expr: Expression = input.parse().unwrap();
+    let antiderivative = expr
+        .integrate()
+        .unwrap_or_else(|| panic!("no antiderivative for {}", input));
+    let derivative = antiderivative.derivative();
+    for x in [0.3, 0.7, 1.1, 1.4] {
+        let (expected, actual) = (expr.evaluate(x), derivative.evaluate(x));
+        assert!(
+            (expected - actual).abs() <= 1e-9 * expected.abs().max(1.0),
+            "d/dx of ∫ {} = {} at {}: expected {}, got {}",
+            input,
+            antiderivative,
+            x,
+            expected,
+            actual
+        );
+    }
+}
+
+#[test]
+fn polynomials_and_elementary_functions() {
+    for input in [
+        "3x^2 - 2x + 1",
+        "exp(x)",
+        "ln(x)",
+        "log(x, 10)",
+        "sqrt(x)",
+        "x^-1",
+        "x^0.5",
+        "x^-2.5",
+        "sinh(x)",
+        "cosh(x)",
+    ] {
+        assert_antiderivative(input);
+    }
+}
+
+#[test]
+fn trigonometric_table() {
+    for input in [
+        "sin(x)",
+        "cos(x)",
+        "tan(x)",
+        "cot(x)",
+        "sec(x)",
+        "cosec(x)",
+        "sec(x)^2",
+        "cosec(x)^2",
+        "sin(x)^2",
+        "cos(x)^2",
+        "sin(x)^3 * cos(x)",
+        "sin(x) * cos(x)^2",
+        "tan(x)^2 * sec(x)^2",
+        "cot(x)^2 * cosec(x)^2",
+        "tan(x) * sec(x)^3",
+        "cot(x) * cosec(x)^2",
+        "sec(x) * tan(x)",
+    ] {
+        assert_antiderivative(input);
+    }
+}
+
+#[test]
+fn linear_arguments_sums_and_constant_factors() {
+    for input in [
+        "sin(2x + 1)",
+        "3cos(0.5x - 2)",
+        "sec(3x)^2 / 4",
+        "exp(-2x) + sin(x)^2 - x^3",
+    ] {
+        assert_antiderivative(input);
+    }
+}
+
+#[test]
+fn substitution() {
+    for input in [
+        "2x * cos(x^2)",
+        "x * exp(x^2)",
+        "3x^2 * sin(x^3 + 1)",
+        "(2x + 1) * sqrt(x^2 + x)",
+        "x * (x^2 + 1)^-2",
+    ] {
+        assert_antiderivative(input);
+    }
+}
+
+#[test]
+fn integration_by_parts() {
+    for input in ["x * sin(x)", "x^2 * cos(2x + 1)", "(x^3 - x) * sin(3x)"] {
+        assert_antiderivative(input);
+    }
+}
+
+#[test]
+fn unsupported_integrands() {
+    for input in [
+        "sin(x) / x",
+        "exp(x^2)",
+        "asin(x)",
+        "x * tan(x)",
+        "sin(cos(x))",
+    ] {
+        let expr: Expression = input.parse().unwrap();
+        assert_eq!(expr.integrate(), None, "{}", input);
+    }
+}
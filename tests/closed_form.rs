@@ -0,0 +1,211 @@
+use num_complex::Complex64;
+
+use expression::math::{
+    closed_form::{cubic, quadratic, quartic, Solution},
+    func_traits::VariableFunction,
+    polynomial::Polynomial,
+};
+
+/// The values sorted by real and then imaginary part, checked against
+/// `expected` and against the expressions of the real roots.
+fn assert_solutions(solutions: &[Solution], expected: &[Complex64], tolerance: f64) {
+    let mut values = solutions.iter().map(|s| s.value).collect::<Vec<_>>();
+    values.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+    assert_eq!(values.len(), expected.len());
+    for (value, expected) in values.iter().zip(expected) {
+        assert!(
+            (value - expected).norm() <= tolerance * expected.norm().max(1.0),
+            "{:?} != {:?}",
+            values,
+            expected
+        );
+    }
+    for solution in solutions {
+        if let Some(expression) = &solution.expression {
+            let value = expression.evaluate(0.0);
+            assert!(
+                (value - solution.value.re).abs() <= 1e-12 * value.abs().max(1.0),
+                "{} = {}, expected {}",
+                expression,
+                value,
+                solution.value
+            );
+        }
+    }
+}
+
+fn real(values: &[f64]) -> Vec<Complex64> {
+    values.iter().map(|v| Complex64::new(*v, 0.0)).collect()
+}
+
+#[test]
+fn quadratic_avoids_cancellation() {
+    // x^2 - 1e8 x + 1, the small root 1e-8 cancels to zero in the textbook
+    // formula.
+    let solutions = quadratic(1.0, -1e8, 1.0);
+    assert_solutions(&solutions, &real(&[1e-8, 1e8]), 1e-15);
+    let small = solutions
+        .iter()
+        .map(|s| s.value.re)
+        .fold(f64::INFINITY, f64::min);
+    assert!((small - 1e-8).abs() <= 1e-15 * 1e-8);
+
+    assert_solutions(&quadratic(1.0, 1e8, 1.0), &real(&[-1e8, -1e-8]), 1e-15);
+    assert_solutions(&quadratic(2.0, -4.0, 2.0), &real(&[1.0, 1.0]), 1e-15);
+    assert_solutions(
+        &quadratic(1.0, 2.0, 5.0),
+        &[Complex64::new(-1.0, -2.0), Complex64::new(-1.0, 2.0)],
+        1e-15,
+    );
+    assert_solutions(&quadratic(0.0, 2.0, -3.0), &real(&[1.5]), 1e-15);
+}
+
+#[test]
+fn cubic_with_one_real_root() {
+    // x^3 - 1, Cardano's formula with a positive discriminant.
+    let half = 3f64.sqrt() / 2.0;
+    assert_solutions(
+        &cubic(1.0, 0.0, 0.0, -1.0),
+        &[
+            Complex64::new(-0.5, -half),
+            Complex64::new(-0.5, half),
+            Complex64::new(1.0, 0.0),
+        ],
+        1e-14,
+    );
+    // (x + 2)(x^2 + 1) = x^3 + 2x^2 + x + 2.
+    assert_solutions(
+        &cubic(1.0, 2.0, 1.0, 2.0),
+        &[
+            Complex64::new(-2.0, 0.0),
+            Complex64::new(0.0, -1.0),
+            Complex64::new(0.0, 1.0),
+        ],
+        1e-14,
+    );
+}
+
+#[test]
+fn cubic_with_three_real_roots() {
+    // (x - 1)(x - 2)(x - 3), the trigonometric form.
+    let solutions = cubic(1.0, -6.0, 11.0, -6.0);
+    assert_solutions(&solutions, &real(&[1.0, 2.0, 3.0]), 1e-14);
+    assert!(solutions.iter().all(|s| s.expression.is_some()));
+    assert_solutions(
+        &cubic(2.0, 0.0, -6.0, 0.0),
+        &real(&[-3f64.sqrt(), 0.0, 3f64.sqrt()]),
+        1e-14,
+    );
+}
+
+#[test]
+fn cubic_with_repeated_roots() {
+    // (x - 1)^3, `p = q = 0` after the shift.
+    assert_solutions(&cubic(1.0, -3.0, 3.0, -1.0), &real(&[1.0; 3]), 1e-15);
+    // (x - 1)^2 (x + 2), a vanishing discriminant.
+    assert_solutions(&cubic(1.0, 0.0, -3.0, 2.0), &real(&[-2.0, 1.0, 1.0]), 1e-15);
+}
+
+#[test]
+fn quartic_by_ferrari() {
+    // (x - 1)(x - 2)(x - 3)(x - 4)
+    assert_solutions(
+        &quartic(1.0, -10.0, 35.0, -50.0, 24.0),
+        &real(&[1.0, 2.0, 3.0, 4.0]),
+        1e-13,
+    );
+    // (x^2 + 1)(x - 1)(x + 3) = x^4 + 2x^3 - 2x^2 + 2x - 3.
+    assert_solutions(
+        &quartic(1.0, 2.0, -2.0, 2.0, -3.0),
+        &[
+            Complex64::new(-3.0, 0.0),
+            Complex64::new(0.0, -1.0),
+            Complex64::new(0.0, 1.0),
+            Complex64::new(1.0, 0.0),
+        ],
+        1e-13,
+    );
+}
+
+#[test]
+fn biquadratic_quartic() {
+    // x^4 - 5x^2 + 4 = (x^2 - 1)(x^2 - 4), `q = 0`.
+    let solutions = quartic(1.0, 0.0, -5.0, 0.0, 4.0);
+    assert_solutions(&solutions, &real(&[-2.0, -1.0, 1.0, 2.0]), 1e-15);
+    assert!(solutions.iter().all(|s| s.expression.is_some()));
+
+    let r = std::f64::consts::FRAC_1_SQRT_2;
+    assert_solutions(
+        &quartic(1.0, 0.0, 0.0, 0.0, 1.0),
+        &[
+            Complex64::new(-r, -r),
+            Complex64::new(-r, r),
+            Complex64::new(r, -r),
+            Complex64::new(r, r),
+        ],
+        1e-15,
+    );
+}
+
+#[test]
+fn quartic_with_a_tiny_linear_term() {
+    // y^4 + 5y^2 + q y + 4 with the roots near ±i and ±2i. The resolvent
+    // root is about q^2 / 18 and vanishes to rounding for small q.
+    for q in [1e-3, 1e-8, 1e-12, 1e-160, 1e-200] {
+        let solutions = quartic(1.0, 0.0, 5.0, q, 4.0);
+        assert_eq!(solutions.len(), 4);
+        for solution in &solutions {
+            let z = solution.value;
+            assert!(z.is_finite(), "q = {}: {:?}", q, solutions);
+            let residual = z.powu(4) + 5.0 * z * z + q * z + 4.0;
+            assert!(residual.norm() <= 1e-13, "q = {}: {:?}", q, solutions);
+        }
+    }
+}
+
+#[test]
+fn quartic_expressions_match_their_values() {
+    let cases = [
+        [1.0, -10.0, 35.0, -50.0, 24.0],
+        [1.0, 2.0, -2.0, 2.0, -3.0],
+        [3.0, -1.0, -7.0, 2.0, 0.5],
+        [1.0, 1.0, -10.0, 0.1, 1.0],
+        // The resolvent root is polished well away from Cardano's value.
+        [0.001, 1.0, 1.0, 1.0, 1.0],
+    ];
+    for [a, b, c, d, e] in cases {
+        for solution in quartic(a, b, c, d, e) {
+            let z = solution.value;
+            if let Some(expression) = &solution.expression {
+                let value = expression.evaluate(0.0);
+                assert!(
+                    (value - z.re).abs() <= 1e-14 * value.abs().max(1.0),
+                    "{:?}: {} = {}, expected {}",
+                    [a, b, c, d, e],
+                    expression,
+                    value,
+                    z
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn solve_dispatches_on_the_degree() {
+    let p = Polynomial {
+        poly: vec![-6_i32, 11, -6, 1, 0],
+        deg: 4,
+    };
+    assert_solutions(&p.solve().unwrap(), &real(&[1.0, 2.0, 3.0]), 1e-14);
+    let quintic = Polynomial {
+        poly: vec![1.0; 6],
+        deg: 5,
+    };
+    assert_eq!(quintic.solve(), None);
+    let zero = Polynomial {
+        poly: vec![0.0],
+        deg: 0,
+    };
+    assert_eq!(zero.solve(), None);
+}
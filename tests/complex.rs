@@ -0,0 +1,148 @@
+use std::f64::consts::PI;
+
+use expression::math::{
+    complex::{Complex, Complex64},
+    expression::Expression,
+    latex::ToLatex,
+    polynomial::Polynomial,
+    variable::{Env, EvaluationError},
+};
+
+fn parse(input: &str) -> Expression {
+    input.parse().unwrap()
+}
+
+fn c(re: f64, im: f64) -> Complex64 {
+    Complex64::new(re, im)
+}
+
+fn assert_close(actual: Complex64, expected: Complex64) {
+    assert!(
+        (actual - expected).norm() <= 1e-14 * expected.norm().max(1.0),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn elementary_functions_off_the_real_axis() {
+    let i = Complex64::i();
+    // sin(i) = i sinh(1), cos(i) = cosh(1).
+    assert_close(parse("sin(x)").evaluate_complex(i), c(0.0, 1f64.sinh()));
+    assert_close(parse("cos(x)").evaluate_complex(i), c(1f64.cosh(), 0.0));
+    // ln(-1) = i pi on the principal branch, e^(i pi) = -1.
+    assert_close(parse("ln(x)").evaluate_complex(c(-1.0, 0.0)), c(0.0, PI));
+    assert_close(parse("exp(x)").evaluate_complex(c(0.0, PI)), c(-1.0, 0.0));
+    assert_close(parse("sqrt(x)").evaluate_complex(c(-4.0, 0.0)), c(0.0, 2.0));
+    assert_close(parse("x^2 + 1").evaluate_complex(i), c(0.0, 0.0));
+}
+
+#[test]
+fn other_variables_off_the_real_axis() {
+    let i = Complex64::i();
+    let expr = parse("exp(t x) + y");
+    assert!(expr.evaluate_complex(i).is_nan());
+    let env = Env::new().with("t", PI).with("y", 1.0);
+    assert_close(expr.evaluate_complex_with(i, &env).unwrap(), c(0.0, 0.0));
+    assert_eq!(
+        expr.evaluate_complex_with(i, &Env::new().with("t", PI)),
+        Err(EvaluationError::Unbound("y".to_string()))
+    );
+    // Real-only nodes bind `x` to the real point and are NaN elsewhere.
+    let expr = parse("max(x, y)");
+    assert_close(
+        expr.evaluate_complex_with(c(3.0, 0.0), &env).unwrap(),
+        c(3.0, 0.0),
+    );
+    assert!(expr.evaluate_complex_with(i, &env).unwrap().is_nan());
+}
+
+/// `i x^3 + (-1 + 2i) x^2 - 2i x + 1`.
+fn p() -> Polynomial<Complex64> {
+    Polynomial {
+        poly: vec![c(1.0, 0.0), c(0.0, -2.0), c(-1.0, 2.0), c(0.0, 1.0)],
+        deg: 3,
+    }
+}
+
+#[test]
+fn complex_polynomial_evaluation_and_derivative() {
+    let p = p();
+    assert_eq!(p.evaluate(Complex64::i()), c(5.0, -2.0));
+    assert_eq!(p.evaluate(c(0.0, 0.0)), c(1.0, 0.0));
+    assert_eq!(p.evaluate_at(c(0.0, 0.0)), c(1.0, 0.0));
+    assert_eq!(
+        p.derivative().poly,
+        vec![c(0.0, -2.0), c(-2.0, 4.0), c(0.0, 3.0)]
+    );
+
+    let integral = p.integral(c(0.0, 1.0));
+    assert_eq!(integral.deg, 4);
+    assert_eq!(integral.derivative().poly, p.poly);
+    assert_eq!(integral.evaluate(c(0.0, 0.0)), c(0.0, 1.0));
+    // Gaussian integer coefficients are promoted like integers.
+    let gaussian = Polynomial {
+        poly: vec![Complex::new(1_i32, 2), Complex::new(0, 3)],
+        deg: 1,
+    };
+    assert_eq!(
+        gaussian.integral(c(0.0, 0.0)).poly,
+        vec![c(0.0, 0.0), c(1.0, 2.0), c(0.0, 1.5)]
+    );
+}
+
+#[test]
+fn complex_polynomial_arithmetic() {
+    let (p, i) = (p(), Complex64::i());
+    let q = Polynomial {
+        poly: vec![c(0.0, 1.0), c(1.0, 0.0)],
+        deg: 1,
+    };
+    let z = c(0.7, -1.3);
+    for (result, expected) in [
+        ((&p + &q).evaluate(z), p.evaluate(z) + q.evaluate(z)),
+        ((p.clone() - &q).evaluate(z), p.evaluate(z) - q.evaluate(z)),
+        ((p.clone() * &q).evaluate(z), p.evaluate(z) * q.evaluate(z)),
+        ((p.clone() * i).evaluate(z), p.evaluate(z) * i),
+        ((i * &p).evaluate(z), i * p.evaluate(z)),
+        ((i + p.clone()).evaluate(z), i + p.evaluate(z)),
+        ((i - &p).evaluate(z), i - p.evaluate(z)),
+        ((p.clone() - i).evaluate(z), p.evaluate(z) - i),
+        ((p.clone() / i).evaluate(z), p.evaluate(z) / i),
+    ] {
+        assert_close(result, expected);
+    }
+    // (x + i)(x - i) = x^2 + 1.
+    let conjugate = Polynomial {
+        poly: vec![c(0.0, -1.0), c(1.0, 0.0)],
+        deg: 1,
+    };
+    assert_eq!(
+        (q * &conjugate).poly,
+        vec![c(1.0, 0.0), c(0.0, 0.0), c(1.0, 0.0)]
+    );
+}
+
+#[test]
+fn complex_polynomial_display() {
+    let p = p();
+    assert_eq!(p.to_string(), "i x^3 + (-1 + 2i)x^2 - 2i x + 1");
+    assert_eq!(
+        format!("{:.1}", p),
+        "i x^3 + (-1.0 + 2.0i)x^2 - 2.0i x + 1.0"
+    );
+    assert_eq!(
+        p.to_latex(),
+        "i x^{3} + \\left(-1 + 2i\\right)x^{2} - 2i x + 1"
+    );
+    assert_eq!(
+        (Complex64::i() + &p).to_string(),
+        "i x^3 + (-1 + 2i)x^2 - 2i x + 1 + i"
+    );
+    let real = Polynomial {
+        poly: vec![c(-1.0, 0.0), c(0.0, 0.0), c(1.0, 0.0)],
+        deg: 2,
+    };
+    assert_eq!(real.to_string(), "x^2 - 1");
+}
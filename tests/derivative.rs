@@ -0,0 +1,392 @@
+use expression::math::{
+    custom::{Custom, CustomFunction, Registry},
+    expression::Expression,
+    func_traits::VariableFunction,
+    function_type::FunctionType,
+    hyperbolic::{HyperbolicFunction, InverseHyperbolicFunction},
+    inverse_trigonometric::InverseTrigonometricFunction,
+    special::SpecialFunction,
+    variable::Env,
+};
+
+/// Central difference approximation of `expr'(x)`.
+fn numeric_derivative(expr: &Expression, x: f64) -> f64 {
+    let h = 1e-5 * x.abs().max(1.0);
+    (expr.evaluate(x + h) - expr.evaluate(x - h)) / (2.0 * h)
+}
+
+fn assert_derivative(input: &str) {
+    assert_derivative_with(input, &Registry::new());
+}
+
+fn assert_derivative_with(input: &str, registry: &Registry) {
+    let expr = registry.parse(input).unwrap();
+    let derivative = expr.derivative();
+    for x in [-1.3, -0.4, 0.3, 0.9, 1.7] {
+        let expected = numeric_derivative(&expr, x);
+        let actual = derivative.evaluate(x);
+        assert!(
+            (expected - actual).abs() <= 1e-5 * expected.abs().max(1.0),
+            "d/dx {} at {}: expected {}, got {} from {}",
+            input,
+            x,
+            expected,
+            actual,
+            derivative
+        );
+        let simplified = derivative.simplify().evaluate(x);
+        assert!(
+            (simplified - actual).abs() <= 1e-9 * actual.abs().max(1.0),
+            "simplified d/dx {} at {}: expected {}, got {}",
+            input,
+            x,
+            actual,
+            simplified
+        );
+    }
+}
+
+#[test]
+fn polynomial_input() {
+    assert_derivative("3x^3 - 2x + 1");
+    assert_derivative("sin(x^2 + 1)");
+    assert_derivative("cos(2.5x^3 + 5x^2 - 2.5)");
+    assert_derivative("sin(x) * cos(2.5x^3 + 5x^2 - 2.5)");
+}
+
+#[test]
+fn constant_input() {
+    assert_derivative("sin(2)");
+    assert_derivative("x + cos(3)");
+}
+
+#[test]
+fn sum_input() {
+    assert_derivative("sin(x + cos(x))");
+    assert_derivative("tan(sin(x) + x^2)");
+}
+
+#[test]
+fn product_input() {
+    assert_derivative("cos(x sin(x))");
+    assert_derivative("sin(2 cos(x) tan(x / 3))");
+}
+
+#[test]
+fn quotient_input() {
+    assert_derivative("tan(sin(x) / (x^2 + 2))");
+    assert_derivative("x / (x + 3)");
+    assert_derivative("cos(x) / sin(x + 2)");
+}
+
+#[test]
+fn nested_functions() {
+    assert_derivative("sin(cos(tan(x / 2)))");
+    assert_derivative("sec(sin(x) + cosec(x + 4))");
+    assert_derivative("cot(x + 2) * sin(cos(x) / (x + 3))");
+}
+
+#[test]
+fn repeated_factors() {
+    assert_derivative("sin(x) * sin(x)");
+    assert_derivative("cos(x) sin(x) cos(x)");
+    assert_derivative("tan(x / 2) * (x + 1) * tan(x / 2) * (x + 1)");
+}
+
+#[test]
+fn second_derivatives() {
+    for input in [
+        "tan(x / 2)",
+        "cot(x + 2)",
+        "sec(x)",
+        "cosec(x + 2)",
+        "sec(x) * tan(x)",
+    ] {
+        let derivative = input.parse::<Expression>().unwrap().derivative();
+        let second = derivative.derivative();
+        for x in [-1.3, -0.4, 0.3, 0.9] {
+            let expected = numeric_derivative(&derivative, x);
+            let actual = second.evaluate(x);
+            assert!(
+                (expected - actual).abs() <= 1e-5 * expected.abs().max(1.0),
+                "d2/dx2 {} at {}: expected {}, got {} from {}",
+                input,
+                x,
+                expected,
+                actual,
+                second
+            );
+        }
+    }
+}
+
+#[test]
+fn powers() {
+    assert_derivative("sin(x)^3");
+    assert_derivative("(x^2 + 1)^0.5");
+    assert_derivative("(x^2 + 2)^-1.5 * cos(x)");
+    assert_derivative("2^x");
+    assert_derivative("(x^2 + 1)^sin(x)");
+    assert_derivative("ln(x^2 + 1)");
+}
+
+#[test]
+fn exponential_and_logarithm() {
+    assert_derivative("exp(x^2) + sqrt(x^2 + 1)");
+    assert_derivative("log(x^2 + 1, 2) * exp(-x)");
+    assert_derivative("log10(x^2 + 0.5) + log2(sqrt(x^2 + 3))");
+    assert_derivative("exp(x) * sqrt(x^2 + 1) * exp(sin(x)) * sqrt(x^2 + 1)");
+}
+
+#[test]
+fn inverse_trigonometric() {
+    assert_derivative("asin(x / 2)");
+    assert_derivative("acos(x / 2) * x");
+    assert_derivative("atan(3x) + acot(x^2)");
+    assert_derivative("asec(x^2 + 1.5)");
+    assert_derivative("acsc(x^2 + 1.5)");
+    assert_derivative("atan2(sin(x), x + 2)");
+}
+
+#[test]
+fn hyperbolic() {
+    assert_derivative("sinh(x) + cosh(2x)");
+    assert_derivative("tanh(x^2) * coth(x + 3)");
+    assert_derivative("sech(x) + csch(x + 2)");
+    assert_derivative("asinh(x) + acosh(x^2 + 1.5)");
+    assert_derivative("atanh(x / 2) + acoth(x + 3)");
+    assert_derivative("asech(x^2 / 4 + 0.1) * acsch(x + 2)");
+}
+
+#[test]
+fn function_families_as_variable_functions() {
+    use HyperbolicFunction as H;
+    use InverseHyperbolicFunction as IH;
+    use InverseTrigonometricFunction as IT;
+
+    fn assert_lambda_derivative<F: VariableFunction<Derivative = FunctionType>>(f: F, x: f64) {
+        let h = 1e-6;
+        let expected = (f.evaluate(x + h) - f.evaluate(x - h)) / (2.0 * h);
+        let derivative = f.derivative();
+        assert!(matches!(derivative, FunctionType::Lambda(_)));
+        assert!((derivative.evaluate(x) - expected).abs() <= 1e-6 * expected.abs().max(1.0));
+    }
+    for f in [
+        H::Sine,
+        H::Cosine,
+        H::Tangent,
+        H::Cotangent,
+        H::Secant,
+        H::Cosecant,
+    ] {
+        assert_lambda_derivative(f, 0.7);
+    }
+    for f in [IH::Arsine, IH::Artangent, IH::Arsecant, IH::Arcosecant] {
+        assert_lambda_derivative(f, 0.6);
+    }
+    for f in [IH::Arcosine, IH::Arcotangent] {
+        assert_lambda_derivative(f, 1.8);
+    }
+    for f in [IT::Arcsine, IT::Arccosine, IT::Arctangent, IT::Arccotangent] {
+        assert_lambda_derivative(f, 0.6);
+    }
+    for f in [IT::Arcsecant, IT::Arccosecant] {
+        assert_lambda_derivative(f, -1.8);
+    }
+    for f in [
+        SpecialFunction::Gamma,
+        SpecialFunction::Erf,
+        SpecialFunction::BesselY1,
+    ] {
+        assert_lambda_derivative(f, 1.4);
+    }
+}
+
+#[test]
+fn special_functions() {
+    assert_derivative("erf(x) + erfc(2x) + normcdf(x^2)");
+    assert_derivative("tgamma(x + 3) + lgamma(x^2 + 1) * digamma(x + 2)");
+    assert_derivative("besselj0(x) * besselj1(2x)");
+    assert_derivative("bessely0(x + 2) + bessely1(x^2 + 1)");
+    assert_derivative("lambertw(x^2) + lambertw(x / 4)");
+}
+
+#[test]
+fn piecewise() {
+    assert_derivative("abs(x - 1) * sin(x)");
+    assert_derivative("sign(x - 0.5) * x^2 + heaviside(x) * cos(x)");
+    assert_derivative("min(x, 0.5) + max(x^2, 1)");
+    assert_derivative("clamp(2x, -1, 1) * exp(x)");
+    let derivative = "abs(x)".parse::<Expression>().unwrap().derivative();
+    assert!(derivative.evaluate(0.0).is_nan());
+}
+
+#[derive(Debug)]
+struct Sigmoid;
+
+impl CustomFunction for Sigmoid {
+    fn name(&self) -> &str {
+        "sigmoid"
+    }
+
+    fn evaluate(&self, x: f64) -> f64 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    fn derivative(&self) -> Option<Expression> {
+        let sigmoid = Custom::new(Sigmoid).of(Expression::variable("x"));
+        Some(&sigmoid * (1.0 - &sigmoid))
+    }
+}
+
+/// No symbolic derivative, so it is differentiated numerically.
+#[derive(Debug)]
+struct Softplus;
+
+impl CustomFunction for Softplus {
+    fn name(&self) -> &str {
+        "softplus"
+    }
+
+    fn evaluate(&self, x: f64) -> f64 {
+        x.exp().ln_1p()
+    }
+}
+
+#[test]
+fn custom_functions() {
+    let registry = Registry::new().with(Sigmoid).with(Softplus);
+    assert_derivative_with("sigmoid(x^2) * sin(x)", &registry);
+    assert_derivative_with("softplus(2x) + sigmoid(softplus(x))", &registry);
+    let expr = registry.parse("softplus(x)").unwrap();
+    assert_eq!(expr.to_string(), "softplus(x)");
+    assert_eq!(expr.derivative().simplify().to_string(), "softplus'(x)");
+    assert!((expr.derivative().evaluate(0.7) - Sigmoid.evaluate(0.7)).abs() < 1e-9);
+    assert!("softplus(x)".parse::<Expression>().is_err());
+
+    // Numeric derivatives print with primes and parse back, nesting them
+    // costs accuracy.
+    let second = registry.parse("softplus''(x)").unwrap();
+    assert_eq!(second, expr.derivative().derivative().simplify());
+    assert_eq!(second.to_string(), "softplus''(x)");
+    let derivative = 0.7f64.exp() / (1.0 + 0.7f64.exp()).powi(2);
+    assert!((second.evaluate(0.7) - derivative).abs() < 1e-4);
+    let parsed = registry.parse("sigmoid'(x)").unwrap();
+    assert_eq!(
+        parsed.evaluate(0.7),
+        Sigmoid.derivative().unwrap().evaluate(0.7)
+    );
+    assert!(registry.parse("x'").is_err());
+    assert!(registry.parse("sin'(x)").is_err());
+}
+
+#[test]
+fn custom_functions_are_equal_by_handle() {
+    let x = || Expression::variable("x");
+    let (a, b) = (Custom::new(Softplus), Custom::new(Softplus));
+    assert_eq!(a, a.clone());
+    assert_ne!(a, b);
+    assert_eq!(a.derivative(), a.derivative());
+    // Only factors of the same function are merged.
+    assert_eq!(
+        (a.of(x()) * a.of(x())).simplify().to_string(),
+        "softplus(x)^2"
+    );
+    assert_eq!(
+        (a.of(x()) * b.of(x())).simplify().to_string(),
+        "softplus(x) * softplus(x)"
+    );
+    let square = Expression::power(a.of(x()), Expression::constant(2.0));
+    assert_eq!(
+        square.derivative().simplify().to_string(),
+        "2 * softplus(x) * softplus'(x)"
+    );
+}
+
+#[test]
+fn partial_derivatives() {
+    let expr: Expression = "x^2 y + sin(x y) + y^x + t".parse().unwrap();
+    let at = |x: f64, y: f64| Env::new().with("x", x).with("y", y).with("t", 2.0);
+    let f = |x: f64, y: f64| expr.evaluate_with(&at(x, y)).unwrap();
+    let h = 1e-5;
+    for (x, y) in [(0.7, 1.3), (1.1, 0.4), (2.0, 2.5)] {
+        let expected = [
+            (f(x + h, y) - f(x - h, y)) / (2.0 * h),
+            (f(x, y + h) - f(x, y - h)) / (2.0 * h),
+            0.0,
+        ];
+        for (partial, expected) in expr.gradient(&["x", "y", "z"]).iter().zip(expected) {
+            let actual = partial.evaluate_with(&at(x, y)).unwrap();
+            assert!(
+                (expected - actual).abs() <= 1e-5 * expected.abs().max(1.0),
+                "{} at ({}, {}): expected {}, got {}",
+                partial,
+                x,
+                y,
+                expected,
+                actual
+            );
+        }
+
+        let hessian = expr.hessian(&["x", "y"]);
+        let mixed =
+            (f(x + h, y + h) - f(x + h, y - h) - f(x - h, y + h) + f(x - h, y - h)) / (4.0 * h * h);
+        for partial in [&hessian[0][1], &hessian[1][0]] {
+            let actual = partial.simplify().evaluate_with(&at(x, y)).unwrap();
+            assert!(
+                (mixed - actual).abs() <= 1e-4 * mixed.abs().max(1.0),
+                "{} at ({}, {}): expected {}, got {}",
+                partial,
+                x,
+                y,
+                mixed,
+                actual
+            );
+        }
+    }
+}
+
+#[test]
+fn jacobian_of_a_vector_valued_function() {
+    let functions =
+        ["x y", "sin(x) + y^2", "exp(x - 2y)"].map(|input| input.parse::<Expression>().unwrap());
+    let jacobian = Expression::jacobian(&functions, &["x", "y"]);
+    assert_eq!(jacobian.len(), 3);
+    for (x, y) in [(0.7, 1.3), (-1.1, 0.4)] {
+        let env = Env::new().with("x", x).with("y", y);
+        let expected = [
+            [y, x],
+            [x.cos(), 2.0 * y],
+            [(x - 2.0 * y).exp(), -2.0 * (x - 2.0 * y).exp()],
+        ];
+        for (row, expected) in jacobian.iter().zip(expected) {
+            assert_eq!(row.len(), 2);
+            for (partial, expected) in row.iter().zip(expected) {
+                let actual = partial.evaluate_with(&env).unwrap();
+                assert!(
+                    (expected - actual).abs() <= 1e-12 * expected.abs().max(1.0),
+                    "{} at ({}, {}): expected {}, got {}",
+                    partial,
+                    x,
+                    y,
+                    expected,
+                    actual
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn partial_derivative_with_respect_to_an_absent_variable() {
+    for input in [
+        "x^2 y + sin(x y)",
+        "exp(t) * cos(omega)",
+        "3",
+        "besselj0(x)",
+    ] {
+        let expr: Expression = input.parse().unwrap();
+        let partial = expr.derivative_wrt("z");
+        assert_eq!(partial.simplify(), Expression::constant(0.0), "{}", input);
+    }
+}
@@ -0,0 +1,193 @@
+use expression::math::{
+    expression::Expression,
+    func_traits::VariableFunction,
+    function_type::FunctionType,
+    integration::{gauss_kronrod, QuadratureError},
+    inverse_trigonometric::InverseTrigonometricFunction,
+    variable::{Env, EvaluationError},
+};
+
+#[test]
+fn empty_sums_and_products() {
+    let (sum, product) = (Expression::sum(Vec::new()), Expression::product(Vec::new()));
+    assert_eq!(sum.evaluate(2.0), 0.0);
+    assert_eq!(product.evaluate(2.0), 1.0);
+    assert_eq!(Expression::new_from(&[]).evaluate(2.0), 1.0);
+    assert_eq!(sum.derivative().evaluate(2.0), 0.0);
+    assert_eq!(product.derivative().evaluate(2.0), 0.0);
+
+    let env = Env::new().with("x", 2.0);
+    assert_eq!(sum.evaluate_with(&env), Ok(0.0));
+    assert_eq!(product.evaluate_with(&env), Ok(1.0));
+}
+
+#[test]
+fn evaluate_with_bindings() {
+    let expr: Expression = "sin(omega t + phi) + x^2".parse().unwrap();
+    let env = Env::new()
+        .with("omega", 2.0)
+        .with("t", 0.5)
+        .with("phi", 0.25)
+        .with("x", 3.0);
+    assert_eq!(expr.evaluate_with(&env), Ok(1.25f64.sin() + 9.0));
+
+    let env: Env = [("t", 2.0), ("x", 1.0)].into_iter().collect();
+    let tagged = Expression::polynomial_in(
+        "x^2 + 1"
+            .parse::<Expression>()
+            .unwrap()
+            .as_polynomial()
+            .unwrap(),
+        "t",
+    );
+    assert_eq!(tagged.to_string(), "t^2 + 1");
+    assert_eq!(tagged.evaluate_with(&env), Ok(5.0));
+    assert_eq!(Expression::variable("x").evaluate_with(&env), Ok(1.0));
+}
+
+#[test]
+fn unbound_variables_are_reported() {
+    let expr: Expression = "sin(omega t) * x".parse().unwrap();
+    let env = Env::new().with("t", 1.0).with("x", 1.0);
+    assert_eq!(
+        expr.evaluate_with(&env),
+        Err(EvaluationError::Unbound("omega".to_string()))
+    );
+    assert_eq!(
+        expr.evaluate_with(&env).unwrap_err().to_string(),
+        "variable `omega` is not bound"
+    );
+    // Polynomials are in `x`, which must be bound as well.
+    assert_eq!(
+        "x^2"
+            .parse::<Expression>()
+            .unwrap()
+            .evaluate_with(&Env::new()),
+        Err(EvaluationError::Unbound("x".to_string()))
+    );
+}
+
+#[test]
+fn plain_evaluate_is_nan_on_other_variables() {
+    assert!("x + t"
+        .parse::<Expression>()
+        .unwrap()
+        .evaluate(1.0)
+        .is_nan());
+    let expr: Expression = "x/(y z)".parse().unwrap();
+    assert!(expr.evaluate(1.0).is_nan());
+    assert!(expr.derivative().evaluate(1.0).is_nan());
+    assert_eq!(
+        gauss_kronrod(&expr, 0.0, 1.0, 1e-10, 50).unwrap_err(),
+        QuadratureError::NonFinite
+    );
+    let env = Env::new().with("x", 1.0).with("y", 2.0).with("z", 4.0);
+    assert_eq!(expr.evaluate_with(&env), Ok(0.125));
+}
+
+#[test]
+fn substitute_in_multivariate_expressions() {
+    let expr: Expression = "x^2 y + sin(t) * x".parse().unwrap();
+    let env = Env::new()
+        .with("x", 1.5)
+        .with("y", -0.5)
+        .with("t", 0.3)
+        .with("u", 2.0);
+
+    // y -> u^2 + x leaves the other variables alone.
+    let replaced = expr.substitute("y", &"u^2 + x".parse().unwrap());
+    let expected = 1.5f64.powi(2) * (4.0 + 1.5) + 0.3f64.sin() * 1.5;
+    assert!((replaced.evaluate_with(&env).unwrap() - expected).abs() <= 1e-12);
+    assert!(!replaced.depends_on("y"));
+
+    // x -> x + 1 composes into the polynomial inputs.
+    let shifted = expr.substitute("x", &"x + 1".parse().unwrap());
+    let expected = 2.5f64.powi(2) * -0.5 + 0.3f64.sin() * 2.5;
+    assert!((shifted.evaluate_with(&env).unwrap() - expected).abs() <= 1e-12);
+
+    // x -> t, the result no longer depends on x.
+    let renamed = expr.substitute("x", &Expression::variable("t"));
+    assert!(!renamed.depends_on("x"));
+    let expected = 0.09 * -0.5 + 0.3f64.sin() * 0.3;
+    assert!((renamed.evaluate_with(&env).unwrap() - expected).abs() <= 1e-12);
+
+    // Substituting a variable that does not occur changes nothing.
+    assert_eq!(
+        expr.substitute("z", &Expression::constant(1.0))
+            .evaluate_with(&env),
+        expr.evaluate_with(&env)
+    );
+}
+
+#[test]
+fn domains_of_logarithms_roots_and_powers() {
+    let cases = [
+        (FunctionType::Logarithm, [0.5, 1e-300], [0.0, -1.0]),
+        (FunctionType::LogarithmBase(2.0), [8.0, 1.0], [0.0, -8.0]),
+        (FunctionType::SquareRoot, [0.0, 4.0], [-1e-300, -4.0]),
+        (FunctionType::Power(0.5), [0.0, 2.0], [-2.0, -0.5]),
+        (FunctionType::Power(-1.5), [1e-3, 2.0], [0.0, -2.0]),
+        (FunctionType::Power(-2.0), [-3.0, 3.0], [0.0, f64::NAN]),
+    ];
+    for (function, inside, outside) in cases {
+        for x in inside {
+            assert!(function.domain_contains(x), "{} at {}", function, x);
+            assert!(function.evaluate(x).is_finite(), "{} at {}", function, x);
+        }
+        for x in outside {
+            assert!(!function.domain_contains(x), "{} at {}", function, x);
+            assert!(!function.evaluate(x).is_finite(), "{} at {}", function, x);
+        }
+    }
+}
+
+#[test]
+fn domains_of_inverse_trigonometric_functions() {
+    use InverseTrigonometricFunction as IT;
+    let cases = [
+        (IT::Arcsine, [-1.0, 0.5], [1.0 + 1e-12, -2.0]),
+        (IT::Arccosine, [1.0, -0.5], [-1.0 - 1e-12, 2.0]),
+        (IT::Arcsecant, [1.0, -2.0], [0.5, -1.0 + 1e-12]),
+        (IT::Arccosecant, [-1.0, 2.0], [0.0, -0.5]),
+    ];
+    for (function, inside, outside) in cases {
+        for x in inside {
+            assert!(function.domain_contains(x), "{} at {}", function, x);
+            assert!(function.evaluate(x).is_finite(), "{} at {}", function, x);
+        }
+        for x in outside {
+            assert!(!function.domain_contains(x), "{} at {}", function, x);
+            assert!(function.evaluate(x).is_nan(), "{} at {}", function, x);
+        }
+    }
+    assert!(IT::Arctangent.domain_contains(1e300));
+    assert!(!IT::Arctangent.domain_contains(f64::NAN));
+}
+
+#[test]
+fn piecewise_branch_selection() {
+    let cases = [
+        ("abs(x - 1)", [(-1.0, 2.0), (1.0, 0.0), (3.0, 2.0)]),
+        ("sign(x)", [(-2.0, -1.0), (0.0, 0.0), (0.5, 1.0)]),
+        ("heaviside(x)", [(-2.0, 0.0), (0.0, 0.5), (0.5, 1.0)]),
+        ("min(x, 1)", [(-2.0, -2.0), (1.0, 1.0), (3.0, 1.0)]),
+        ("max(x, 1)", [(-2.0, 1.0), (1.0, 1.0), (3.0, 3.0)]),
+        ("clamp(x, -1, 1)", [(-2.0, -1.0), (0.5, 0.5), (3.0, 1.0)]),
+        (
+            "{x if x == 0; 2 if x > 0; 3 otherwise}",
+            [(0.0, 0.0), (5.0, 2.0), (-5.0, 3.0)],
+        ),
+    ];
+    for (input, points) in cases {
+        let expr: Expression = input.parse().unwrap();
+        for (x, expected) in points {
+            assert_eq!(expr.evaluate(x), expected, "{} at {}", input, x);
+            let env = Env::new().with("x", x);
+            assert_eq!(expr.evaluate_with(&env), Ok(expected), "{} at {}", input, x);
+        }
+    }
+    // Conditions on other variables are read from the environment.
+    let expr: Expression = "max(x, y)".parse().unwrap();
+    let env = Env::new().with("x", 1.0).with("y", 2.0);
+    assert_eq!(expr.evaluate_with(&env), Ok(2.0));
+}
@@ -0,0 +1,87 @@
+use std::f64::consts::PI;
+
+use expression::math::{
+    expression::Expression,
+    integration::{gauss_kronrod, gauss_legendre, simpson, QuadratureError},
+};
+
+fn parse(input: &str) -> Expression {
+    input.parse().unwrap()
+}
+
+#[test]
+fn sine_over_half_period() {
+    let sine = parse("sin(x)");
+    for result in [
+        simpson(&sine, 0.0, PI, 64),
+        gauss_legendre(&sine, 0.0, PI, 10),
+        gauss_kronrod(&sine, 0.0, PI, 1e-12, 50),
+    ] {
+        let result = result.unwrap();
+        assert!((result.value - 2.0).abs() <= 1e-7, "{:?}", result);
+    }
+    let reversed = gauss_kronrod(&sine, PI, 0.0, 1e-12, 50).unwrap();
+    assert!((reversed.value + 2.0).abs() <= 1e-12);
+}
+
+#[test]
+fn gaussian_over_the_real_line() {
+    let gaussian = parse("exp(-x^2)");
+    let result = gauss_kronrod(&gaussian, f64::NEG_INFINITY, f64::INFINITY, 1e-10, 200).unwrap();
+    assert!((result.value - PI.sqrt()).abs() <= 1e-9, "{:?}", result);
+
+    let half = gauss_kronrod(&gaussian, 0.0, f64::INFINITY, 1e-10, 200).unwrap();
+    assert!((half.value - PI.sqrt() / 2.0).abs() <= 1e-9, "{:?}", half);
+}
+
+#[test]
+fn error_estimates_bound_the_actual_error() {
+    let f = parse("exp(x) * cos(3x)");
+    // Antiderivative exp(x) (cos(3x) + 3 sin(3x)) / 10.
+    let exact = (2f64.exp() * ((6f64).cos() + 3.0 * (6f64).sin()) - 1.0) / 10.0;
+    for result in [
+        simpson(&f, 0.0, 2.0, 16),
+        gauss_legendre(&f, 0.0, 2.0, 6),
+        gauss_kronrod(&f, 0.0, 2.0, 1e-8, 50),
+    ] {
+        let result = result.unwrap();
+        let actual = (result.value - exact).abs();
+        assert!(
+            actual <= result.error.max(1e-14),
+            "{:?} vs {}",
+            result,
+            exact
+        );
+        assert!(result.error <= 1e-2, "{:?}", result);
+    }
+    let result = gauss_kronrod(&f, 0.0, 2.0, 1e-8, 50).unwrap();
+    assert!(result.error <= 1e-8);
+}
+
+#[test]
+fn non_convergence_reports_the_best_estimate() {
+    // 1/sqrt(x) is integrable on (0, 1] but too singular to meet the
+    // tolerance with a handful of intervals.
+    let f = parse("1 / sqrt(x)");
+    match gauss_kronrod(&f, 0.0, 1.0, 1e-14, 4) {
+        Err(QuadratureError::NotConverged(best)) => {
+            assert!((best.value - 2.0).abs() < 0.1, "{:?}", best);
+            assert!(best.error > 1e-14);
+        }
+        other => panic!("expected NotConverged, got {:?}", other),
+    }
+}
+
+#[test]
+fn invalid_bounds_and_non_finite_integrands() {
+    let f = parse("x");
+    assert_eq!(
+        gauss_kronrod(&f, f64::NAN, 1.0, 1e-8, 10),
+        Err(QuadratureError::InvalidInterval)
+    );
+    assert_eq!(
+        gauss_kronrod(&parse("1 / x"), -1.0, 1.0, 1e-8, 10),
+        Err(QuadratureError::NonFinite)
+    );
+    assert_eq!(simpson(&f, 1.0, 1.0, 8).unwrap().value, 0.0);
+}
@@ -0,0 +1,62 @@
+use expression::math::{expression::Expression, func_traits::VariableFunction};
+use expression::{cos, sin, x};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= 1e-12 * expected.abs().max(1.0),
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn polynomial_operands_are_merged() {
+    let x = Expression::from(x!());
+    let sum = &x + 1.0;
+    assert_eq!(sum.as_polynomial().unwrap().poly, vec![1.0, 1.0]);
+    let product = (&x + 1.0) * (&x - 1.0);
+    assert_eq!(product.as_polynomial().unwrap().poly, vec![-1.0, 0.0, 1.0]);
+    let quotient = (2.0 * &x + 4.0) / 2.0;
+    assert_eq!(quotient.as_polynomial().unwrap().poly, vec![2.0, 1.0]);
+    assert_eq!((-&x).as_polynomial().unwrap().poly, vec![0.0, -1.0]);
+}
+
+#[test]
+fn operators_match_evaluation() {
+    let x = Expression::from(x!());
+    let s = sin!().of(x.clone());
+    let c = cos!().of(x.clone());
+    for point in [-1.3, 0.4, 2.1] {
+        let (sv, cv) = (s.evaluate(point), c.evaluate(point));
+        assert_close((&s + &c).evaluate(point), sv + cv);
+        assert_close((&s - &c).evaluate(point), sv - cv);
+        assert_close((&s * &c).evaluate(point), sv * cv);
+        assert_close((&s / &c).evaluate(point), sv / cv);
+        assert_close((-&s).evaluate(point), -sv);
+        assert_close((s.clone() + &x).evaluate(point), sv + point);
+        assert_close((2.0 - &s).evaluate(point), 2.0 - sv);
+        assert_close((2.0 / &s).evaluate(point), 2.0 / sv);
+        assert_close((&s - 2.0).evaluate(point), sv - 2.0);
+        assert_close((x!() * &s).evaluate(point), point * sv);
+        assert_close((&s * x!()).evaluate(point), sv * point);
+    }
+}
+
+#[test]
+fn nested_sums_and_products_are_flattened() {
+    let x = Expression::from(x!());
+    let s = sin!().of(x.clone());
+    let sum = &s + 1.0 + &x + 2.0;
+    assert_eq!(sum.to_string(), "sin(x) + x + 3");
+    let product = 2.0 * &s * 3.0 * &s;
+    assert_eq!(product.to_string(), "6 * sin(x) * sin(x)");
+}
+
+#[test]
+fn division_by_constant_zero_is_kept() {
+    let x = Expression::from(x!());
+    let quotient = &x / 0.0;
+    assert!(quotient.as_polynomial().is_none());
+    assert_eq!(quotient.evaluate(1.0), f64::INFINITY);
+}
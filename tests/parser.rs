@@ -0,0 +1,123 @@
+use expression::math::{expression::Expression, func_traits::VariableFunction};
+
+fn parse(input: &str) -> Expression {
+    input.parse().unwrap()
+}
+
+#[test]
+fn large_integer_powers_stay_unexpanded() {
+    let cube = parse("(x + 1)^3").as_polynomial().unwrap();
+    assert_eq!(cube.poly, vec![1.0, 3.0, 3.0, 1.0]);
+
+    for input in ["x^20000", "x^1e9", "(x + 1)^65"] {
+        let expr = parse(input);
+        assert!(expr.as_polynomial().is_none(), "{} was expanded", input);
+    }
+    assert_eq!(parse("x^1e9").evaluate(1.0), 1.0);
+    assert_eq!(parse("(x + 1)^65").evaluate(1.0), 2f64.powi(65));
+}
+
+/// Asserts that both inputs parse to the same function of `x`.
+fn assert_same(input: &str, expected: &str) {
+    let (actual, expected_expr) = (parse(input), parse(expected));
+    for x in [-1.3, -0.4, 0.3, 0.9, 1.7] {
+        let (a, b) = (actual.evaluate(x), expected_expr.evaluate(x));
+        assert!(
+            (a - b).abs() <= 1e-12 * b.abs().max(1.0),
+            "{} and {} differ at {}: {} != {}",
+            input,
+            expected,
+            x,
+            a,
+            b
+        );
+    }
+}
+
+#[test]
+fn power_precedence_and_associativity() {
+    assert_eq!(parse("2^3^2").evaluate(0.0), 512.0);
+    assert_eq!(parse("(2^3)^2").evaluate(0.0), 64.0);
+    assert_eq!(parse("2^-1").evaluate(0.0), 0.5);
+    assert_same("x^2^3", "x^8");
+    assert_same("2x^2", "2 * (x^2)");
+    assert_same("x * x^2 + 1", "(x^3) + 1");
+}
+
+#[test]
+fn unary_minus_binds_below_power() {
+    assert_eq!(parse("-x^2").evaluate(3.0), -9.0);
+    assert_eq!(parse("-2^2").evaluate(0.0), -4.0);
+    assert_eq!(parse("(-2)^2").evaluate(0.0), 4.0);
+    assert_same("--x", "x");
+    assert_same("x - -x", "2x");
+}
+
+#[test]
+fn implicit_multiplication() {
+    assert_same("2x", "2 * x");
+    assert_same("2.5x^3", "2.5 * x^3");
+    assert_same("2sin(x)", "2 * sin(x)");
+    assert_same("(x + 1)(x - 1)", "x^2 - 1");
+    assert_same("2(x + 1)", "2x + 2");
+    assert_same("x sin(x)", "x * sin(x)");
+    // Implicit products have the precedence of `*`.
+    assert_same("1 / 2x", "x / 2");
+}
+
+#[test]
+fn number_followed_by_e() {
+    assert_eq!(parse("2e").evaluate(0.0), 2.0 * std::f64::consts::E);
+    assert_eq!(parse("2e3").evaluate(0.0), 2000.0);
+    assert_eq!(parse("2E-1").evaluate(0.0), 0.2);
+    assert_same("2e x", "2 * e * x");
+}
+
+#[test]
+fn errors_point_at_the_offending_input() {
+    let cases = [
+        ("x + ", 4..4, "unexpected end of input"),
+        ("x $ 1", 2..3, "unexpected character `$`"),
+        ("x )", 2..3, "unexpected `)`"),
+        ("(x + 1", 6..6, "expected `)`, found end of input"),
+        ("foo(x)", 0..3, "unknown function `foo`"),
+        ("sin x", 4..5, "expected `(` after function `sin`"),
+        ("1.2.3", 0..5, "invalid number `1.2.3`"),
+        (
+            "log(x, x)",
+            7..8,
+            "the base of a logarithm must be constant",
+        ),
+        ("atan2(x)", 7..8, "expected `,`, found `)`"),
+        (
+            "{x; 1 otherwise}",
+            2..3,
+            "expected `if` or `otherwise`, found `;`",
+        ),
+        (
+            "{x if x 0; 1 otherwise}",
+            8..9,
+            "expected a comparison, found number `0`",
+        ),
+        (
+            "{x if x < 0 otherwise}",
+            12..21,
+            "expected `;`, found `otherwise`",
+        ),
+        ("{x otherwise", 12..12, "expected `}`, found end of input"),
+        ("x ! 1", 2..3, "unexpected character `!`"),
+    ];
+    for (input, span, message) in cases {
+        let error = input.parse::<Expression>().unwrap_err();
+        assert_eq!(
+            (error.span, error.message.as_str()),
+            (span, message),
+            "{}",
+            input
+        );
+    }
+    assert_eq!(
+        "x + ".parse::<Expression>().unwrap_err().to_string(),
+        "unexpected end of input at 4..4"
+    );
+}
@@ -0,0 +1,208 @@
+use expression::math::polynomial::{Polynomial, PolynomialOperationTypes};
+
+fn polynomial<T: PolynomialOperationTypes + Copy>(poly: &[T]) -> Polynomial<T> {
+    Polynomial {
+        poly: poly.to_vec(),
+        deg: poly.len() as u32 - 1,
+    }
+}
+
+#[test]
+fn integer_coefficients_integrate_to_f64() {
+    // 3x^2 + 2x + 1 integrates to x^3 + x^2 + x + 5.
+    let integral: Polynomial<f64> = polynomial(&[1_i32, 2, 3]).integral(5.0);
+    assert_eq!(integral.poly, vec![5.0, 1.0, 1.0, 1.0]);
+    assert_eq!(integral.deg, 3);
+
+    // x^2 integrates to x^3 / 3, which integer coefficients cannot hold.
+    let integral = polynomial(&[0_u8, 0, 1]).integral(0.0);
+    assert_eq!(integral.poly, vec![0.0, 0.0, 0.0, 1.0 / 3.0]);
+
+    let integral = polynomial(&[2.0_f32, 4.0]).integral(1.0);
+    assert_eq!(integral.poly, vec![1.0_f32, 2.0, 2.0]);
+}
+
+#[test]
+fn definite_integral() {
+    let p = polynomial(&[1_i64, 2, 3]);
+    assert_eq!(p.definite_integral(0.0, 1.0), 3.0);
+    assert_eq!(p.definite_integral(1.0, 0.0), -3.0);
+    assert_eq!(p.definite_integral(-1.0, 1.0), 4.0);
+    assert_eq!(
+        polynomial(&[0.0, 0.0, 1.0]).definite_integral(0.0, 3.0),
+        9.0
+    );
+    assert_eq!(polynomial(&[0.0]).definite_integral(-2.0, 5.0), 0.0);
+}
+
+#[test]
+fn add_assign_and_sub_assign() {
+    let mut p = polynomial(&[1.0, 2.0, 3.0]);
+    p += &polynomial(&[1.0, 1.0, 1.0]);
+    assert_eq!((p.poly.as_slice(), p.deg), ([2.0, 3.0, 4.0].as_slice(), 2));
+
+    p += &polynomial(&[1.0, 1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(
+        (p.poly.as_slice(), p.deg),
+        ([3.0, 4.0, 5.0, 1.0, 1.0].as_slice(), 4)
+    );
+
+    p += &polynomial(&[1.0]);
+    assert_eq!(p.poly, vec![4.0, 4.0, 5.0, 1.0, 1.0]);
+
+    let mut p = polynomial(&[1.0, 2.0, 3.0]);
+    p -= &polynomial(&[1.0, 1.0, 1.0]);
+    assert_eq!((p.poly.as_slice(), p.deg), ([0.0, 1.0, 2.0].as_slice(), 2));
+
+    p -= &polynomial(&[1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(
+        (p.poly.as_slice(), p.deg),
+        ([-1.0, 0.0, 1.0, -1.0].as_slice(), 3)
+    );
+
+    p -= &polynomial(&[1.0]);
+    assert_eq!(p.poly, vec![-2.0, 0.0, 1.0, -1.0]);
+}
+
+#[test]
+fn scalar_operators_on_the_left() {
+    let p = polynomial(&[1.0, 2.0, 3.0]);
+    assert_eq!((5.0_f64 - &p).poly, vec![4.0, -2.0, -3.0]);
+    assert_eq!((5.0_f64 - p.clone()).poly, vec![4.0, -2.0, -3.0]);
+    assert_eq!((p.clone() - 5.0).poly, vec![-4.0, 2.0, 3.0]);
+    assert_eq!((5.0_f64 + &p).poly, vec![6.0, 2.0, 3.0]);
+    assert_eq!((2.0_f64 * &p).poly, vec![2.0, 4.0, 6.0]);
+    assert_eq!((5_i32 - polynomial(&[1_i32, -2])).poly, vec![4, 2]);
+    for x in [-1.5, 0.0, 2.0] {
+        assert_eq!((5.0_f64 - &p).evaluate(x), 5.0 - p.evaluate(x));
+    }
+}
+
+#[test]
+fn compose_shift_and_scale_match_evaluation() {
+    let p = polynomial(&[1.0_f64, -3.0, 0.0, 2.0]);
+    let q = polynomial(&[0.5, 2.0, -1.0]);
+    let composed = p.compose(&q);
+    assert_eq!(composed.deg, 6);
+    let shifted = p.shift(1.5);
+    let scaled = p.scale(-2.0);
+    for x in [-1.7_f64, -0.3, 0.0, 0.8, 2.2] {
+        let expected = p.evaluate(q.evaluate(x));
+        assert!((composed.evaluate(x) - expected).abs() <= 1e-12 * expected.abs().max(1.0));
+        assert!(
+            (shifted.evaluate(x) - p.evaluate(x + 1.5)).abs()
+                <= 1e-12 * p.evaluate(x + 1.5).abs().max(1.0)
+        );
+        assert_eq!(scaled.evaluate(x), p.evaluate(-2.0 * x));
+    }
+    // Composing with a constant gives a constant, with x gives p back.
+    assert_eq!(p.compose(&polynomial(&[2.0])).poly, vec![p.evaluate(2.0)]);
+    assert_eq!(p.compose(&polynomial(&[0.0, 1.0])).poly, p.poly);
+    assert_eq!(polynomial(&[1_i32, 1]).shift(1).poly, vec![2, 1]);
+}
+
+fn assert_coefficients_close(actual: &Polynomial<f64>, expected: &[f64]) {
+    assert_eq!(actual.deg as usize, expected.len() - 1, "{:?}", actual.poly);
+    for (a, e) in actual.poly.iter().zip(expected) {
+        assert!(
+            (a - e).abs() <= 1e-12,
+            "{:?} != {:?}",
+            actual.poly,
+            expected
+        );
+    }
+}
+
+#[test]
+fn division_with_remainder() {
+    // x^4 - 3x^3 + 2x + 5 by 2x^2 + x - 1.
+    let a = polynomial(&[5.0, 2.0, 0.0, -3.0, 1.0]);
+    let b = polynomial(&[-1.0, 1.0, 2.0]);
+    let (q, r) = a.div_rem(&b).unwrap();
+    assert_eq!(q.deg, 2);
+    assert!(r.deg < b.deg);
+    let identity = &(q.clone() * &b) + &r;
+    assert_coefficients_close(&identity, &a.poly);
+    assert_eq!((&a / &b).poly, q.poly);
+    assert_eq!((&a % &b).poly, r.poly);
+
+    // A divisor of higher degree leaves the dividend as the remainder.
+    let (q, r) = b.div_rem(&a).unwrap();
+    assert!(q.is_zero());
+    assert_eq!(r.poly, b.poly);
+
+    // Exact division leaves no remainder.
+    let (q, r) = polynomial(&[-1.0, 0.0, 1.0])
+        .div_rem(&polynomial(&[1.0, 1.0]))
+        .unwrap();
+    assert_eq!((q.poly, r.is_zero()), (vec![-1.0, 1.0], true));
+}
+
+#[test]
+fn division_by_zero() {
+    let a = polynomial(&[1.0, 2.0]);
+    assert!(a.div_rem(&polynomial(&[0.0])).is_none());
+    assert!(a.div_rem(&polynomial(&[0.0, 0.0])).is_none());
+}
+
+#[test]
+#[should_panic(expected = "division by the zero polynomial")]
+fn division_operator_panics_on_zero() {
+    let _ = polynomial(&[1.0, 2.0]) / &polynomial(&[0.0]);
+}
+
+#[test]
+#[should_panic(expected = "division by the zero polynomial")]
+fn remainder_operator_panics_on_zero() {
+    let _ = &polynomial(&[1.0, 2.0]) % &polynomial(&[0.0]);
+}
+
+#[test]
+fn gcd_with_a_shared_factor() {
+    // (x - 1)(x + 2) and 3(x - 1)(x - 3) share x - 1.
+    let a = polynomial(&[-2.0, 1.0, 1.0]);
+    let b = polynomial(&[9.0, -12.0, 3.0]);
+    assert_coefficients_close(&a.gcd(&b), &[-1.0, 1.0]);
+    assert_coefficients_close(&b.gcd(&a), &[-1.0, 1.0]);
+
+    // Coprime polynomials have the gcd 1, the zero polynomial is neutral.
+    assert_coefficients_close(&a.gcd(&polynomial(&[1.0, 1.0])), &[1.0]);
+    assert_coefficients_close(&b.gcd(&polynomial(&[0.0])), &[3.0, -4.0, 1.0]);
+    assert!(polynomial(&[0.0]).gcd(&polynomial(&[0.0])).is_zero());
+}
+
+#[test]
+fn bezout_identity() {
+    let cases = [
+        (
+            polynomial(&[-2.0, 1.0, 1.0]),
+            polynomial(&[9.0, -12.0, 3.0]),
+        ),
+        (polynomial(&[1.0, 0.0, 1.0]), polynomial(&[-1.0, 1.0])),
+        (
+            polynomial(&[0.0, -1.0, 0.0, 1.0]),
+            polynomial(&[1.0, 2.0, 1.0]),
+        ),
+    ];
+    for (a, b) in cases {
+        let (g, s, t) = a.extended_gcd(&b);
+        assert_eq!(g.poly[g.deg as usize], 1.0);
+        let combination = (&(s * &a) + &(t * &b)).normalized();
+        assert_coefficients_close(&combination, &g.poly);
+    }
+}
+
+#[test]
+fn lcm() {
+    // (x - 1)(x + 2) and (x - 1)(x - 3) give (x - 1)(x + 2)(x - 3).
+    let a = polynomial(&[-2.0, 1.0, 1.0]);
+    let b = polynomial(&[9.0, -12.0, 3.0]);
+    let lcm = a.lcm(&b);
+    assert_coefficients_close(&lcm, &[6.0, -5.0, -2.0, 1.0]);
+    // gcd * lcm = a * b up to the leading coefficient.
+    let product = a.gcd(&b) * &lcm;
+    assert_coefficients_close(&product, &(a.clone() * &b).monic().unwrap().poly);
+
+    assert_coefficients_close(&a.lcm(&a), &a.poly);
+    assert!(a.lcm(&polynomial(&[0.0])).is_zero());
+}
@@ -0,0 +1,119 @@
+use expression::math::{expression::Expression, func_traits::VariableFunction, latex::ToLatex};
+
+fn parse(input: &str) -> Expression {
+    input.parse().unwrap()
+}
+
+#[test]
+fn brackets_only_where_needed() {
+    let cases = [
+        ("-(sin(x) + 1)", "-(sin(x) + 1)"),
+        ("-(t + 1)", "-(t + 1)"),
+        ("(sin(x) + 1)^2", "(sin(x) + 1)^2"),
+        ("(t - 1)(t + 1)", "(t - 1) * (t + 1)"),
+        ("t - (t - 1)", "t - (t - 1)"),
+        ("x^(x^2)", "x^(x^2)"),
+        ("(x^x)^2", "(x^x)^2"),
+        ("t^(2^t)", "t^(2^t)"),
+        ("(-x)^x", "(-x)^x"),
+        ("x^-1", "x^(-1)"),
+        ("-x^2", "-x^2"),
+        ("x / (2 * sin(x))", "x / (2 * sin(x))"),
+        ("2 - (-x) * sin(x)", "2 + x * sin(x)"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(parse(input).to_string(), expected, "{}", input);
+    }
+}
+
+#[test]
+fn polynomials_in_descending_order() {
+    assert_eq!(
+        parse("x^3 + 2x - 5 + 4x^2").to_string(),
+        "x^3 + 4x^2 + 2x - 5"
+    );
+    assert_eq!(parse("1 - x^2").to_string(), "-x^2 + 1");
+    assert_eq!(parse("(x + 1)^2").to_latex(), "x^{2} + 2x + 1");
+}
+
+#[test]
+fn latex_fractions_roots_and_powers() {
+    let cases = [
+        ("sin(x) / (x + 1)", r"\frac{\sin\left(x\right)}{x + 1}"),
+        ("sqrt(x + 1)", r"\sqrt{x + 1}"),
+        ("1 / sqrt(x)", r"\frac{1}{\sqrt{x}}"),
+        ("(x^x)^2", r"\left(x^{x}\right)^{2}"),
+        ("t^(2^t)", r"t^{2^{t}}"),
+        ("-(t + 1)", r"-\left(t + 1\right)"),
+        (
+            "sqrt(sin(x)) / (2 * t)",
+            r"\frac{\sqrt{\sin\left(x\right)}}{2 t}",
+        ),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(parse(input).to_latex(), expected, "{}", input);
+    }
+}
+
+#[test]
+fn precision_applies_to_constants() {
+    assert_eq!(format!("{:.3}", parse("pi * x")), "3.142x");
+    assert_eq!(format!("{:.3}", parse("x^2 + 2x")), "x^2 + 2.000x");
+    assert_eq!(format!("{:.2}", parse("sin(x) / 3")), "sin(x) / 3.00");
+    assert_eq!(parse("pi * x").to_string(), "3.141592653589793x");
+}
+
+#[test]
+fn piecewise_cases_form() {
+    let cases = [
+        ("abs(x - 1)", "{-x + 1 if x - 1 < 0; x - 1 otherwise}"),
+        ("sign(x)", "{-1 if x < 0; 0 if x = 0; 1 otherwise}"),
+        ("heaviside(t)", "{0 if t < 0; 0.5 if t = 0; 1 otherwise}"),
+        ("min(x, 2)", "{x if x <= 2; 2 otherwise}"),
+        ("max(x^2, y)", "{x^2 if x^2 >= y; y otherwise}"),
+        (
+            "clamp(2x, -1, 1)",
+            "{-1 if 2x < -1; 1 if 2x > 1; 2x otherwise}",
+        ),
+        ("3 abs(x)^2", "3 * {-x if x < 0; x otherwise}^2"),
+    ];
+    for (input, expected) in cases {
+        let expr = parse(input);
+        assert_eq!(expr.to_string(), expected, "{}", input);
+        assert_eq!(parse(expected), expr, "{}", input);
+    }
+    assert_eq!(
+        parse("min(x, 2)").to_latex(),
+        "\\begin{cases} x & x \\le 2 \\\\ 2 & \\text{otherwise} \\end{cases}"
+    );
+}
+
+#[test]
+fn plain_output_parses_back() {
+    for input in [
+        "sin(x) * cos(2.5x^3 + 5x^2 - 2.5) / (x + 1)",
+        "-(sin(x) + 1)^2 - x^(x^2)",
+        "exp(-x^2) / 2 + ln(x^2 + 1)^2",
+        "2^(x / 3) - sqrt(x^2 + 1) * atan(x)",
+        "(x^x)^2 / (1 - x^3)",
+        "atan2(x, 2) + erf(x) * tgamma(x + 1)",
+        "sinh(x) / cosh(x)^2",
+        "abs(x - 1) * sin(x) + clamp(2x, -1, 1) / max(x, sign(x - 2))",
+    ] {
+        let expr = parse(input);
+        let printed = expr.to_string();
+        let reparsed = parse(&printed);
+        for x in [0.3, 0.9, 1.7, 2.4] {
+            let (expected, actual) = (expr.evaluate(x), reparsed.evaluate(x));
+            assert!(
+                (expected - actual).abs() <= 1e-12 * expected.abs().max(1.0),
+                "{} printed as {} differs at {}: {} != {}",
+                input,
+                printed,
+                x,
+                expected,
+                actual
+            );
+        }
+    }
+}
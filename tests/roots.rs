@@ -0,0 +1,174 @@
+use num_complex::Complex64;
+
+use expression::math::{
+    polynomial::Polynomial,
+    roots::{Root, RootError, RootOptions},
+};
+
+/// `prod (x - r)` over `roots`.
+fn from_roots(roots: &[f64]) -> Polynomial<f64> {
+    roots.iter().fold(
+        Polynomial {
+            poly: vec![1.0],
+            deg: 0,
+        },
+        |p, r| {
+            p * &Polynomial {
+                poly: vec![-r, 1.0],
+                deg: 1,
+            }
+        },
+    )
+}
+
+fn assert_roots(actual: &[Root], expected: &[(Complex64, u32)], tolerance: f64) {
+    assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+    for (root, (value, multiplicity)) in actual.iter().zip(expected) {
+        assert!(
+            (root.value - value).norm() <= tolerance,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+        assert_eq!(root.multiplicity, *multiplicity, "{:?}", actual);
+    }
+}
+
+fn real(value: f64, multiplicity: u32) -> (Complex64, u32) {
+    (Complex64::new(value, 0.0), multiplicity)
+}
+
+#[test]
+fn repeated_roots_are_merged() {
+    let roots = from_roots(&[1.0, 1.0, 1.0]).roots().unwrap();
+    assert_roots(&roots, &[real(1.0, 3)], 1e-10);
+    assert_eq!(roots[0].value.im, 0.0);
+
+    let roots = from_roots(&[1.0, 1.0, 2.0, 2.0]).roots().unwrap();
+    assert_roots(&roots, &[real(1.0, 2), real(2.0, 2)], 1e-10);
+}
+
+#[test]
+fn complex_conjugate_pairs() {
+    // (x^2 + 1)(x^2 - 2x + 5) has the roots ±i and 1 ± 2i.
+    let p = Polynomial {
+        poly: vec![5.0, -2.0, 6.0, -2.0, 1.0],
+        deg: 4,
+    };
+    let roots = p.roots().unwrap();
+    assert_roots(
+        &roots,
+        &[
+            (Complex64::new(0.0, -1.0), 1),
+            (Complex64::new(0.0, 1.0), 1),
+            (Complex64::new(1.0, -2.0), 1),
+            (Complex64::new(1.0, 2.0), 1),
+        ],
+        1e-12,
+    );
+    assert_eq!(roots[0].value, roots[1].value.conj());
+    assert_eq!(roots[2].value, roots[3].value.conj());
+    assert!(p.real_roots().unwrap().is_empty());
+}
+
+#[test]
+fn leading_and_trailing_zero_coefficients() {
+    // 0 x^4 + x^3 - x^2 + 0 x + 0, i.e. x^2 (x - 1).
+    let p = Polynomial {
+        poly: vec![0.0, 0.0, -1.0, 1.0, 0.0],
+        deg: 4,
+    };
+    let roots = p.roots().unwrap();
+    assert_roots(&roots, &[real(0.0, 2), real(1.0, 1)], 1e-14);
+    assert_eq!(roots.iter().map(|r| r.multiplicity).sum::<u32>(), 3);
+
+    let constant = Polynomial {
+        poly: vec![2.0, 0.0],
+        deg: 1,
+    };
+    assert_eq!(constant.roots(), Ok(Vec::new()));
+}
+
+#[test]
+fn wilkinson_like_polynomial() {
+    // The roots 1..=12, already badly conditioned at the larger roots.
+    let expected = (1..=12).map(f64::from).collect::<Vec<_>>();
+    let roots = from_roots(&expected).real_roots().unwrap();
+    assert_eq!(roots.len(), 12, "{:?}", roots);
+    for (root, expected) in roots.iter().zip(&expected) {
+        assert_eq!(root.multiplicity, 1);
+        assert!(
+            (root.value - expected).abs() <= 1e-6 * expected,
+            "{:?}",
+            roots
+        );
+    }
+}
+
+#[test]
+fn roots_are_ordered_by_real_then_imaginary_part() {
+    // (x + 3)(x - 2)(x^2 + 4)(x^2 - 2x + 2)
+    let p = from_roots(&[-3.0, 2.0])
+        * &Polynomial {
+            poly: vec![4.0, 0.0, 1.0],
+            deg: 2,
+        }
+        * &Polynomial {
+            poly: vec![2.0, -2.0, 1.0],
+            deg: 2,
+        };
+    let roots = p.roots().unwrap();
+    assert_roots(
+        &roots,
+        &[
+            real(-3.0, 1),
+            (Complex64::new(0.0, -2.0), 1),
+            (Complex64::new(0.0, 2.0), 1),
+            (Complex64::new(1.0, -1.0), 1),
+            (Complex64::new(1.0, 1.0), 1),
+            real(2.0, 1),
+        ],
+        1e-12,
+    );
+    let real_roots = p.real_roots().unwrap();
+    assert_eq!(
+        real_roots.iter().map(|r| r.value).collect::<Vec<_>>(),
+        roots
+            .iter()
+            .filter(|r| r.value.im == 0.0)
+            .map(|r| r.value.re)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(real_roots.len(), 2);
+}
+
+#[test]
+fn errors() {
+    let zero = Polynomial {
+        poly: vec![0.0, 0.0],
+        deg: 1,
+    };
+    assert_eq!(zero.roots(), Err(RootError::ZeroPolynomial));
+
+    let non_finite = Polynomial {
+        poly: vec![1.0, f64::NAN, 1.0],
+        deg: 2,
+    };
+    assert_eq!(non_finite.roots(), Err(RootError::NonFinite));
+    let infinite = Polynomial {
+        poly: vec![1.0, f64::INFINITY],
+        deg: 1,
+    };
+    assert_eq!(infinite.real_roots(), Err(RootError::NonFinite));
+
+    let options = RootOptions {
+        max_iterations: 0,
+        ..RootOptions::default()
+    };
+    match from_roots(&[1.0, 2.0, 3.0]).roots_with(&options) {
+        Err(RootError::NotConverged(roots)) => {
+            assert_eq!(roots.iter().map(|r| r.multiplicity).sum::<u32>(), 3)
+        }
+        other => panic!("expected NotConverged, got {:?}", other),
+    }
+}
@@ -0,0 +1,93 @@
+use expression::math::{expression::Expression, func_traits::VariableFunction};
+
+fn simplify(input: &str) -> Expression {
+    input.parse::<Expression>().unwrap().simplify()
+}
+
+#[test]
+fn large_integer_powers_stay_unexpanded() {
+    let square = Expression::power(
+        Expression::parse("x + 1").unwrap(),
+        Expression::constant(2.0),
+    );
+    assert_eq!(
+        square.simplify().as_polynomial().unwrap().poly,
+        vec![1.0, 2.0, 1.0]
+    );
+
+    let power = Expression::power(
+        Expression::parse("x").unwrap(),
+        Expression::constant(20000.0),
+    );
+    let simplified = power.simplify();
+    assert!(simplified.as_polynomial().is_none());
+    assert_eq!(simplified.evaluate(1.0), 1.0);
+    assert_eq!(simplified.evaluate(2.0), f64::INFINITY);
+    assert_eq!(simplify("x^1e9 + 0").evaluate(1.0), 1.0);
+}
+
+fn assert_simplifies(input: &str, expected: &str) {
+    let simplified = simplify(input);
+    assert_eq!(simplified.to_string(), expected, "simplify({})", input);
+    assert_eq!(
+        simplified.simplify(),
+        simplified,
+        "simplify is not idempotent on {}",
+        input
+    );
+}
+
+#[test]
+fn constants_are_folded() {
+    assert_simplifies("2 * 3 + 4", "10");
+    assert_simplifies("sin(0) + cos(0)", "1");
+    assert_simplifies("sqrt(16) / 2", "2");
+    assert_simplifies("2 * sin(x) * 3", "6 * sin(x)");
+}
+
+#[test]
+fn identities_are_removed() {
+    assert_simplifies("0 * sin(x)", "0");
+    assert_simplifies("sin(x) * 0", "0");
+    assert_simplifies("1 * sin(x)", "sin(x)");
+    assert_simplifies("sin(x) * 1", "sin(x)");
+    assert_simplifies("sin(x) + 0", "sin(x)");
+    assert_simplifies("0 + sin(x)", "sin(x)");
+    assert_simplifies("sin(x) / 1", "sin(x)");
+    assert_simplifies("sin(x)^1", "sin(x)");
+    assert_simplifies("sin(x)^0", "1");
+    assert_simplifies("(sin(x) + 0) * (1 + 0)", "sin(x)");
+}
+
+#[test]
+fn like_terms_are_collected() {
+    assert_simplifies("x + 2x + 3", "3x + 3");
+    assert_simplifies("sin(x) + 2 + x + 3x", "sin(x) + 4x + 2");
+    assert_simplifies("sin(x) * cos(x) * sin(x)", "sin(x)^2 * cos(x)");
+    assert_simplifies("exp(x) * exp(2x)", "exp(3x)");
+    assert_simplifies("sqrt(x) * sqrt(x)", "x");
+}
+
+#[test]
+fn simplify_is_idempotent() {
+    for input in [
+        "sin(x) * cos(2.5x^3 + 5x^2 - 2.5) / (x + 1)",
+        "ln(x^2 + 1) * exp(-x) + x^3 * sqrt(x)",
+        "(x + 1)^2 * sin(x)^3 / cos(x)",
+        "max(x, 0) + abs(sin(x))",
+    ] {
+        let once = simplify(input);
+        assert_eq!(once.simplify(), once, "{}", input);
+    }
+}
+
+#[test]
+fn constant_folding_propagates_nan() {
+    assert!(simplify("0 / 0").evaluate(1.0).is_nan());
+    assert!(simplify("0 * (1 / 0)").evaluate(1.0).is_nan());
+    assert!(simplify("(1 / 0) * 0").evaluate(1.0).is_nan());
+    assert_eq!(simplify("1 / 0").evaluate(1.0), f64::INFINITY);
+    // A zero absorbs non-constant factors, also where they are undefined.
+    assert_simplifies("0 * ln(x)", "0");
+    assert_simplifies("0 / x", "0");
+}
@@ -0,0 +1,171 @@
+use std::f64::consts::{E, PI};
+
+use expression::math::{func_traits::VariableFunction, special::SpecialFunction};
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance * expected.abs().max(1.0),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn gamma() {
+    use SpecialFunction::{Gamma, LogGamma};
+    assert_close(Gamma.evaluate(5.0), 24.0, 1e-13);
+    assert_close(Gamma.evaluate(1.0), 1.0, 1e-13);
+    assert_close(Gamma.evaluate(0.5), PI.sqrt(), 1e-13);
+    assert_close(Gamma.evaluate(-0.5), -2.0 * PI.sqrt(), 1e-13);
+    assert_close(LogGamma.evaluate(100.0), 359.134_205_369_575_4, 1e-13);
+    assert!(!Gamma.domain_contains(-2.0));
+    assert!(!Gamma.evaluate(-2.0).is_finite());
+}
+
+#[test]
+fn polygamma() {
+    use SpecialFunction::Polygamma;
+    assert_close(Polygamma(0).evaluate(1.0), -0.577_215_664_901_532_9, 1e-14);
+    assert_close(Polygamma(1).evaluate(1.0), PI * PI / 6.0, 1e-14);
+    assert_close(Polygamma(1).evaluate(0.5), PI * PI / 2.0, 1e-14);
+    assert_close(Polygamma(2).evaluate(1.0), -2.404_113_806_319_188_5, 1e-13);
+    // The recurrence `psi(x + 1) = psi(x) + 1/x`.
+    assert_close(
+        Polygamma(0).evaluate(4.5),
+        Polygamma(0).evaluate(3.5) + 1.0 / 3.5,
+        1e-14,
+    );
+}
+
+#[test]
+fn error_functions() {
+    use SpecialFunction::{Erf, Erfc, NormalCdf};
+    assert_close(Erf.evaluate(1.0), 0.842_700_792_949_714_9, 1e-14);
+    assert_close(Erf.evaluate(-1.0), -0.842_700_792_949_714_9, 1e-14);
+    assert_eq!(Erf.evaluate(0.0), 0.0);
+    assert_close(Erfc.evaluate(1.0), 0.157_299_207_050_285_13, 1e-14);
+    // Above the switch to the continued fraction, relative to the tiny value.
+    let erfc_5 = 1.537_459_794_428_034_8e-12;
+    assert!((Erfc.evaluate(5.0) - erfc_5).abs() <= 1e-13 * erfc_5);
+    assert_eq!(Erfc.evaluate(30.0), 0.0);
+
+    assert_eq!(NormalCdf.evaluate(0.0), 0.5);
+    assert_close(NormalCdf.evaluate(1.96), 0.975_002_104_851_779_6, 1e-14);
+    assert_close(NormalCdf.evaluate(-3.0), 0.001_349_898_031_630_094_5, 1e-13);
+}
+
+#[test]
+fn bessel_functions() {
+    use SpecialFunction::{BesselJ0, BesselJ1, BesselY0, BesselY1};
+    let cases = [
+        (BesselJ0, 1.0, 0.765_197_686_557_966_6),
+        (BesselJ0, 10.0, -0.245_935_764_451_348_3),
+        (BesselJ0, 20.0, 0.167_024_664_340_583_15),
+        (BesselJ1, 1.0, 0.440_050_585_744_933_5),
+        (BesselJ1, 10.0, 0.043_472_746_168_861_44),
+        (BesselJ1, 20.0, 0.066_833_124_175_850_05),
+        (BesselY0, 1.0, 0.088_256_964_215_676_96),
+        (BesselY0, 10.0, 0.055_671_167_283_599_39),
+        (BesselY0, 20.0, 0.062_640_596_809_383_83),
+        (BesselY1, 1.0, -0.781_212_821_300_288_7),
+        (BesselY1, 10.0, 0.249_015_424_206_953_9),
+        (BesselY1, 20.0, -0.165_511_614_362_521_3),
+    ];
+    for (function, x, expected) in cases {
+        assert!(
+            (function.evaluate(x) - expected).abs() <= 1e-10,
+            "{}({}) = {}, expected {}",
+            function,
+            x,
+            function.evaluate(x),
+            expected
+        );
+    }
+    assert!(!BesselY0.domain_contains(0.0));
+}
+
+#[test]
+fn bessel_functions_on_both_sides_of_the_switch_to_the_asymptotic_expansion() {
+    use SpecialFunction::{BesselJ0, BesselJ1, BesselY0, BesselY1};
+    // Power series below 14, Hankel's expansion from 14 on.
+    let cases = [
+        (
+            13.999_999_999,
+            [
+                0.171_073_476_243_833_8,
+                0.133_375_154_537_246_56,
+                0.127_192_568_415_538_83,
+                -0.166_644_841_995_268_05,
+            ],
+        ),
+        (
+            14.0,
+            [
+                0.171_073_476_110_458_66,
+                0.133_375_154_698_793_25,
+                0.127_192_568_582_183_7,
+                -0.166_644_841_856_172_27,
+            ],
+        ),
+    ];
+    for (x, expected) in cases {
+        for (function, expected) in [BesselJ0, BesselJ1, BesselY0, BesselY1]
+            .into_iter()
+            .zip(expected)
+        {
+            assert!(
+                (function.evaluate(x) - expected).abs() <= 1e-11,
+                "{}({}) = {}, expected {}",
+                function,
+                x,
+                function.evaluate(x),
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn lambert_w() {
+    use SpecialFunction::LambertW;
+    assert_close(LambertW.evaluate(E), 1.0, 1e-15);
+    assert_eq!(LambertW.evaluate(0.0), 0.0);
+    assert_close(LambertW.evaluate(1.0), 0.567_143_290_409_784, 1e-15);
+    assert_close(LambertW.evaluate(-E.recip()), -1.0, 1e-6);
+    for x in [-0.3, 0.5, 10.0, 1e6] {
+        let w = LambertW.evaluate(x);
+        assert_close(w * w.exp(), x, 1e-14);
+    }
+    assert!(!LambertW.domain_contains(-0.5));
+}
+
+#[test]
+fn derivatives_match_difference_quotients() {
+    use SpecialFunction::*;
+    let h = 1e-6;
+    for (function, x) in [
+        (Gamma, 2.5),
+        (LogGamma, 3.0),
+        (Polygamma(0), 1.5),
+        (Erf, 0.7),
+        (Erfc, 0.7),
+        (NormalCdf, -0.4),
+        (BesselJ0, 3.0),
+        (BesselJ1, 3.0),
+        (BesselY0, 3.0),
+        (BesselY1, 3.0),
+        (LambertW, 2.0),
+    ] {
+        let quotient = (function.evaluate(x + h) - function.evaluate(x - h)) / (2.0 * h);
+        let derivative = function.derivative().evaluate(x);
+        assert!(
+            (derivative - quotient).abs() <= 1e-7 * quotient.abs().max(1.0),
+            "{}'({}) = {}, expected {}",
+            function,
+            x,
+            derivative,
+            quotient
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use expression::math::polynomial::Polynomial;
+
+fn polynomial(poly: &[f64]) -> Polynomial<f64> {
+    Polynomial {
+        poly: poly.to_vec(),
+        deg: poly.len() as u32 - 1,
+    }
+}
+
+/// `(x - 1)(x - 2)(x - 3)`.
+fn cubic() -> Polynomial<f64> {
+    polynomial(&[-6.0, 11.0, -6.0, 1.0])
+}
+
+#[test]
+fn count_real_roots_in_half_open_intervals() {
+    let p = cubic();
+    assert_eq!(p.count_real_roots(f64::NEG_INFINITY, f64::INFINITY), 3);
+    assert_eq!(p.count_real_roots(0.0, 10.0), 3);
+    // `(a, b]` leaves out a root at `a` and keeps one at `b`.
+    assert_eq!(p.count_real_roots(1.0, 3.0), 2);
+    assert_eq!(p.count_real_roots(0.0, 1.0), 1);
+    assert_eq!(p.count_real_roots(1.0, 2.0), 1);
+    assert_eq!(p.count_real_roots(1.0, 1.5), 0);
+    assert_eq!(p.count_real_roots(f64::NEG_INFINITY, 1.0), 1);
+    assert_eq!(p.count_real_roots(3.0, f64::INFINITY), 0);
+    assert_eq!(p.count_real_roots(2.5, f64::INFINITY), 1);
+}
+
+#[test]
+fn count_real_roots_special_cases() {
+    let everywhere = (f64::NEG_INFINITY, f64::INFINITY);
+    // Distinct roots, (x - 1)^2 (x + 1) has two.
+    let repeated = polynomial(&[1.0, -1.0, -1.0, 1.0]);
+    assert_eq!(repeated.count_real_roots(everywhere.0, everywhere.1), 2);
+    assert_eq!(repeated.count_real_roots(0.0, 1.0), 1);
+    assert_eq!(
+        polynomial(&[1.0, 0.0, 1.0]).count_real_roots(everywhere.0, everywhere.1),
+        0
+    );
+    assert_eq!(
+        polynomial(&[3.0]).count_real_roots(everywhere.0, everywhere.1),
+        0
+    );
+    assert_eq!(
+        polynomial(&[0.0, 0.0]).count_real_roots(everywhere.0, everywhere.1),
+        0
+    );
+    // Odd degree with a negative leading coefficient, -x^3 + x.
+    assert_eq!(
+        polynomial(&[0.0, 1.0, 0.0, -1.0]).count_real_roots(everywhere.0, everywhere.1),
+        3
+    );
+    // Integer coefficients are promoted.
+    let integer = Polynomial {
+        poly: vec![-4_i32, 0, 1],
+        deg: 2,
+    };
+    assert_eq!(integer.count_real_roots(0.0, f64::INFINITY), 1);
+}
+
+#[test]
+fn descartes_rule() {
+    assert_eq!(cubic().descartes_rule(), (3, 0));
+    assert_eq!(polynomial(&[1.0, 0.0, 1.0]).descartes_rule(), (0, 0));
+    // x^3 - x, zero coefficients are skipped.
+    assert_eq!(polynomial(&[0.0, -1.0, 0.0, 1.0]).descartes_rule(), (1, 1));
+    // x^2 - x + 1 has no real roots, the bound is exact up to an even number.
+    assert_eq!(polynomial(&[1.0, -1.0, 1.0]).descartes_rule(), (2, 0));
+}
+
+#[test]
+fn root_bounds() {
+    // x^2 - 3x + 2 with the roots 1 and 2.
+    let p = polynomial(&[2.0, -3.0, 1.0]);
+    assert_eq!(p.cauchy_bound(), 4.0);
+    // 2 max(|-3|, |2 / 2|^(1/2))
+    assert_eq!(p.fujiwara_bound(), 6.0);
+
+    let q = polynomial(&[-30.0, 7.0, 0.5, -2.0, 0.25]);
+    let largest = q
+        .roots()
+        .unwrap()
+        .iter()
+        .map(|root| root.value.norm())
+        .fold(0.0, f64::max);
+    assert!(largest <= q.cauchy_bound());
+    assert!(largest <= q.fujiwara_bound());
+
+    assert_eq!(polynomial(&[0.0]).cauchy_bound(), f64::INFINITY);
+    assert_eq!(polynomial(&[0.0]).fujiwara_bound(), f64::INFINITY);
+}
+
+#[test]
+fn isolate_and_refine_real_roots() {
+    let p = cubic();
+    let intervals = p.isolate_real_roots();
+    assert_eq!(intervals.len(), 3);
+    for (interval, root) in intervals.iter().zip([1.0, 2.0, 3.0]) {
+        assert!(interval.0 < root && root <= interval.1, "{:?}", intervals);
+        assert_eq!(p.count_real_roots(interval.0, interval.1), 1);
+        let (a, b) = p.refine_real_root(*interval, 1e-10);
+        assert!(b - a <= 1e-10);
+        assert!(a < root && root <= b, "{:?}", (a, b));
+    }
+    assert!(intervals.windows(2).all(|w| w[0].1 <= w[1].0));
+
+    // Roots 1 and 1 + d are told apart down to about d = 1e-4, closer ones
+    // are taken as a repeated root by the square-free part.
+    let close = |d: f64| polynomial(&[1.0 + d, -2.0 - d, 1.0]);
+    assert_eq!(close(1e-3).isolate_real_roots().len(), 2);
+    assert_eq!(close(1e-6).isolate_real_roots().len(), 1);
+
+    assert!(polynomial(&[1.0, 0.0, 1.0]).isolate_real_roots().is_empty());
+    assert!(polynomial(&[0.0]).isolate_real_roots().is_empty());
+}
+
+#[test]
+fn square_free_part_is_free_of_rounding_noise() {
+    // The GCD of (x - 1)^2 (x + 1) and its derivative is x - (1 - eps), the
+    // division leaves a term of about 1e-16 x that must not survive, or the
+    // sign at the root 1 is decided by rounding.
+    let repeated = polynomial(&[1.0, -1.0, -1.0, 1.0]);
+    let sequence = repeated.sturm_sequence();
+    assert_eq!(sequence[0].poly, vec![-1.0, 0.0, 1.0]);
+    assert_eq!(sequence[0].evaluate(1.0), 0.0);
+    assert_eq!(repeated.count_real_roots(0.0, 1.0), 1);
+    assert_eq!(repeated.count_real_roots(1.0, 2.0), 0);
+    assert_eq!(repeated.count_real_roots(-1.0, 1.0), 1);
+}